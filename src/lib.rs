@@ -58,17 +58,57 @@
 #[macro_use]
 extern crate lazy_static;
 
+// Lets the code generated by the derive macros refer to this crate by name from within it
+#[cfg(feature = "derive")]
+extern crate self as term_table;
+
 pub mod row;
 pub mod table_cell;
 
-use crate::row::Row;
-use crate::table_cell::Alignment;
+use crate::row::{FormatOptions, Row};
+use crate::table_cell::{
+    replace_control_chars, string_width, strip_ansi, Alignment, CenterBias, Color, TableCell,
+    VerticalAlignment, WrapStyle,
+};
 
+use std::borrow::Cow;
 use std::cmp::{max, min};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthChar;
+
+#[cfg(feature = "derive")]
+pub use term_table_derive::TableRow;
 
+/// Creates a row from a list of values that can be converted into cells.
+///
+/// An alignment can be given before the values, separated by a semicolon. It's used
+/// by every cell in the row that doesn't have an alignment of its own.
+///
+/// ```
+/// use term_table::{row, row::Row, table_cell::TableCell, Table};
+///
+/// let table = Table::builder()
+///     .rows(vec![
+///         row!["Fruit name", "Quantity"],
+///         row![align: Right; "Apple", "3"],
+///     ])
+///     .build();
+/// assert_eq!(
+///     table.render(),
+///     "╔════════════╦══════════╗\n\
+///      ║ Fruit name ║ Quantity ║\n\
+///      ╠════════════╬══════════╣\n\
+///      ║      Apple ║        3 ║\n\
+///      ╚════════════╩══════════╝\n"
+/// );
+/// ```
 #[macro_export]
 macro_rules! row {
+    [ align: $align:ident; $($x:expr),* $(,)? ] => {
+        $crate::row![$($x),*].with_alignment($crate::table_cell::Alignment::$align)
+    };
     [ $($x:expr),* ] => {
         Row::new(vec![$(Into::<TableCell>::into($x)),*])
     };
@@ -99,6 +139,123 @@ pub enum RowPosition {
     Last,
 }
 
+/// The characters that end each line of a rendered table
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum LineEnding {
+    /// `\n`, used by Unix-like systems
+    Lf,
+    /// `\r\n`, used by Windows
+    CrLf,
+}
+
+/// How control characters in the content of cells are rendered
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ControlChars {
+    /// Control characters are written as they are
+    Keep,
+    /// Control characters are replaced with `·`
+    Replace,
+    /// Control characters are removed
+    Remove,
+}
+
+/// The measurements of a rendered table, from `Table::layout`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Layout {
+    /// The width of each column, including the padding of its cells
+    pub column_widths: Vec<usize>,
+    /// The number of lines taken up by each row, including `row_padding` but not the separator above it
+    pub row_heights: Vec<usize>,
+    /// The width of the widest line, including the borders
+    pub total_width: usize,
+    /// The number of lines, including the separators, borders, title and caption
+    pub total_height: usize,
+}
+
+/// A set of the lines drawn around and between the cells of a table.
+///
+/// Sets are combined with `|`, as in `Borders::TOP | Borders::BOTTOM`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Borders(u8);
+
+impl Borders {
+    pub const NONE: Borders = Borders(0);
+    pub const TOP: Borders = Borders(1);
+    pub const BOTTOM: Borders = Borders(1 << 1);
+    pub const LEFT: Borders = Borders(1 << 2);
+    pub const RIGHT: Borders = Borders(1 << 3);
+    /// The separators between rows
+    pub const INNER_HORIZONTAL: Borders = Borders(1 << 4);
+    /// The separators between columns
+    pub const INNER_VERTICAL: Borders = Borders(1 << 5);
+    /// The frame around the table
+    pub const OUTER: Borders = Borders(0b1111);
+    pub const INNER: Borders = Borders(0b11_0000);
+    pub const ALL: Borders = Borders(0b11_1111);
+
+    /// Whether every border in `other` is also in this set
+    pub fn contains(self, other: Borders) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for Borders {
+    type Output = Borders;
+
+    fn bitor(self, other: Borders) -> Borders {
+        Borders(self.0 | other.0)
+    }
+}
+
+impl std::ops::BitOrAssign for Borders {
+    fn bitor_assign(&mut self, other: Borders) {
+        self.0 |= other.0;
+    }
+}
+
+impl std::ops::BitAnd for Borders {
+    type Output = Borders;
+
+    fn bitand(self, other: Borders) -> Borders {
+        Borders(self.0 & other.0)
+    }
+}
+
+/// The cells of a table laid out by column, taking cells that span multiple rows into account
+struct Grid {
+    rows: Vec<Vec<GridSlot>>,
+    row_spans: Vec<RowSpan>,
+    /// The number of columns cells start in
+    columns: usize,
+}
+
+/// The contents of a position in a row of the grid
+enum GridSlot {
+    /// The index of a cell in the row and the number of columns it spans
+    Cell(usize, usize),
+    /// The index of a cell spanning multiple rows in `Grid::row_spans`
+    RowSpan(usize),
+    /// Filler before a cell spanning down from a previous row
+    Empty,
+}
+
+/// A cell that spans multiple rows
+struct RowSpan {
+    /// The index of the row containing the cell
+    row: usize,
+    /// The index of the cell in its row
+    cell: usize,
+    /// The first column occupied by the cell
+    column: usize,
+    col_span: usize,
+    /// The index of the last row occupied by the cell
+    last_row: usize,
+}
+
 /// A set of characters which make up a table style
 ///
 ///# Example
@@ -115,10 +272,14 @@ pub enum RowPosition {
 ///     outer_top_horizontal: '╦',
 ///     intersection: '╬',
 ///     vertical: '║',
+///     column_separator: '║',
 ///     horizontal: '═',
+///     merge_fn: None,
+///     horizontal_fill: None,
 /// };
 /// ```
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableStyle {
     pub top_left_corner: char,
     pub top_right_corner: char,
@@ -129,11 +290,29 @@ pub struct TableStyle {
     pub outer_bottom_horizontal: char,
     pub outer_top_horizontal: char,
     pub intersection: char,
+    /// The outer left and right borders
     pub vertical: char,
+    /// The lines between columns. The preset styles use the same character as `vertical`
+    pub column_separator: char,
     pub horizontal: char,
+    /// Chooses the character drawn where a separator meets the separator of the row above it, given the
+    /// character of the row above (`top`), the character of the current row (`bottom`) and the position of the row.
+    ///
+    /// This replaces the built in merging, which only handles the characters of the standard styles
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub merge_fn: Option<fn(top: char, bottom: char, pos: RowPosition) -> char>,
+    /// Characters repeated along horizontal lines instead of `horizontal`, such as `"=-"`.
+    ///
+    /// The pattern is laid out by display width and lined up with the left edge of the table so it
+    /// continues across the columns. Wide characters that don't fit at the end of a line are replaced by `horizontal`
+    pub horizontal_fill: Option<Cow<'static, str>>,
 }
 
 impl TableStyle {
+    pub fn builder() -> TableStyleBuilder {
+        TableStyleBuilder::new()
+    }
+
     /// Basic terminal table style
     ///
     ///# Example
@@ -162,7 +341,10 @@ impl TableStyle {
             outer_top_horizontal: '+',
             intersection: '+',
             vertical: '|',
+            column_separator: '|',
             horizontal: '-',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
@@ -194,10 +376,18 @@ impl TableStyle {
             outer_top_horizontal: '╦',
             intersection: '╬',
             vertical: '║',
+            column_separator: '║',
             horizontal: '═',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
+    /// The double line style. This is the same as `TableStyle::extended`
+    pub fn double() -> TableStyle {
+        TableStyle::extended()
+    }
+
     /// <pre>
     /// ┌─────────────────────────────────────────────────────────────────────────────────┐
     /// │                            This is some centered text                           │
@@ -222,7 +412,10 @@ impl TableStyle {
             outer_top_horizontal: '┬',
             intersection: '┼',
             vertical: '│',
+            column_separator: '│',
             horizontal: '─',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
@@ -250,36 +443,72 @@ impl TableStyle {
             outer_top_horizontal: '┬',
             intersection: '┼',
             vertical: '│',
+            column_separator: '│',
             horizontal: '─',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
     /// <pre>
-    /// ╔─────────────────────────────────────────────────────────────────────────────────╗
-    /// │                            This is some centered text                           │
-    /// ╠────────────────────────────────────────╦────────────────────────────────────────╣
-    /// │ This is left aligned text              │             This is right aligned text │
-    /// ╠────────────────────────────────────────┼────────────────────────────────────────╣
-    /// │ This is left aligned text              │             This is right aligned text │
-    /// ╠────────────────────────────────────────╩────────────────────────────────────────╣
-    /// │ This is some really really really really really really really really really tha │
-    /// │ t is going to wrap to the next line                                             │
-    /// ╚─────────────────────────────────────────────────────────────────────────────────╝
+    /// ┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+    /// ┃                            This is some centered text                           ┃
+    /// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┳━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+    /// ┃ This is left aligned text              ┃             This is right aligned text ┃
+    /// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━╋━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+    /// ┃ This is left aligned text              ┃             This is right aligned text ┃
+    /// ┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┻━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+    /// ┃ This is some really really really really really really really really really tha ┃
+    /// ┃ t is going to wrap to the next line                                             ┃
+    /// ┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
     /// </pre>
+    pub fn heavy() -> TableStyle {
+        TableStyle {
+            top_left_corner: '┏',
+            top_right_corner: '┓',
+            bottom_left_corner: '┗',
+            bottom_right_corner: '┛',
+            outer_left_vertical: '┣',
+            outer_right_vertical: '┫',
+            outer_bottom_horizontal: '┻',
+            outer_top_horizontal: '┳',
+            intersection: '╋',
+            vertical: '┃',
+            column_separator: '┃',
+            horizontal: '━',
+            merge_fn: None,
+            horizontal_fill: None,
+        }
+    }
 
+    /// <pre>
+    /// ╓─────────────────────────────────────────────────────────────────────────────────╖
+    /// ║                            This is some centered text                           ║
+    /// ╟────────────────────────────────────────╥────────────────────────────────────────╢
+    /// ║ This is left aligned text              ║             This is right aligned text ║
+    /// ╟────────────────────────────────────────╫────────────────────────────────────────╢
+    /// ║ This is left aligned text              ║             This is right aligned text ║
+    /// ╟────────────────────────────────────────╨────────────────────────────────────────╢
+    /// ║ This is some really really really really really really really really really tha ║
+    /// ║ t is going to wrap to the next line                                             ║
+    /// ╙─────────────────────────────────────────────────────────────────────────────────╜
+    /// </pre>
     pub fn elegant() -> TableStyle {
         TableStyle {
-            top_left_corner: '╔',
-            top_right_corner: '╗',
-            bottom_left_corner: '╚',
-            bottom_right_corner: '╝',
-            outer_left_vertical: '╠',
-            outer_right_vertical: '╣',
-            outer_bottom_horizontal: '╩',
-            outer_top_horizontal: '╦',
-            intersection: '┼',
-            vertical: '│',
+            top_left_corner: '╓',
+            top_right_corner: '╖',
+            bottom_left_corner: '╙',
+            bottom_right_corner: '╜',
+            outer_left_vertical: '╟',
+            outer_right_vertical: '╢',
+            outer_bottom_horizontal: '╨',
+            outer_top_horizontal: '╥',
+            intersection: '╫',
+            vertical: '║',
+            column_separator: '║',
             horizontal: '─',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
@@ -309,7 +538,10 @@ impl TableStyle {
             outer_top_horizontal: '\0',
             intersection: '\0',
             vertical: '\0',
+            column_separator: '\0',
             horizontal: '\0',
+            merge_fn: None,
+            horizontal_fill: None,
         }
     }
 
@@ -340,10 +572,240 @@ impl TableStyle {
             outer_top_horizontal: ' ',
             intersection: ' ',
             vertical: ' ',
+            column_separator: ' ',
             horizontal: ' ',
+            merge_fn: None,
+            horizontal_fill: None,
+        }
+    }
+
+    /// Table style that looks like a Markdown table.
+    ///
+    /// Combine with `has_top_boarder(false)` and `has_bottom_boarder(false)` to produce
+    /// pasteable Markdown. Adding a `header` and disabling `separate_rows` leaves only the
+    /// separator below the header, as Markdown expects.
+    ///
+    ///# Example
+    ///
+    ///<pre>
+    ///| Name  | Value |
+    ///|-------|-------|
+    ///| Alpha | 1     |
+    ///| Beta  | 2     |
+    ///</pre>
+    pub fn markdown() -> TableStyle {
+        TableStyle {
+            top_left_corner: ' ',
+            top_right_corner: ' ',
+            bottom_left_corner: ' ',
+            bottom_right_corner: ' ',
+            outer_left_vertical: '|',
+            outer_right_vertical: '|',
+            outer_bottom_horizontal: '|',
+            outer_top_horizontal: '|',
+            intersection: '|',
+            vertical: '|',
+            column_separator: '|',
+            horizontal: '-',
+            merge_fn: None,
+            horizontal_fill: None,
+        }
+    }
+
+    /// Table style using only ASCII characters. This is the same as `TableStyle::simple`
+    ///
+    ///# Example
+    ///
+    ///<pre>
+    /// +-------+-------+
+    /// | Name  | Value |
+    /// +-------+-------+
+    /// | Alpha | 1     |
+    /// +-------+-------+
+    ///</pre>
+    pub fn ascii() -> TableStyle {
+        TableStyle::simple()
+    }
+
+    /// Table style that looks like the aligned output of PostgreSQL's `psql`.
+    ///
+    /// Combine with `borders(Borders::INNER_VERTICAL)` and a `header` centered with `header_alignment`
+    /// so that the only horizontal line is the one below the header.
+    ///
+    ///# Example
+    ///
+    ///<pre>
+    ///   Name | Value
+    /// -------+-------
+    ///  Alpha | 1
+    ///  Beta  | 2
+    ///</pre>
+    pub fn psql() -> TableStyle {
+        TableStyle {
+            top_left_corner: '-',
+            top_right_corner: '-',
+            bottom_left_corner: '-',
+            bottom_right_corner: '-',
+            outer_left_vertical: '-',
+            outer_right_vertical: '-',
+            outer_bottom_horizontal: '+',
+            outer_top_horizontal: '+',
+            intersection: '+',
+            vertical: '|',
+            column_separator: '|',
+            horizontal: '-',
+            merge_fn: None,
+            horizontal_fill: None,
+        }
+    }
+
+    /// Finds a preset style by the name of the function that creates it, such as `"thin"` for `TableStyle::thin`.
+    ///
+    /// Returns `None` if there isn't a style with the name
+    pub fn from_name(name: &str) -> Option<TableStyle> {
+        match name {
+            "simple" => Some(TableStyle::simple()),
+            "extended" => Some(TableStyle::extended()),
+            "double" => Some(TableStyle::double()),
+            "thin" => Some(TableStyle::thin()),
+            "heavy" => Some(TableStyle::heavy()),
+            "rounded" => Some(TableStyle::rounded()),
+            "elegant" => Some(TableStyle::elegant()),
+            "blank" => Some(TableStyle::blank()),
+            "empty" => Some(TableStyle::empty()),
+            "markdown" => Some(TableStyle::markdown()),
+            "psql" => Some(TableStyle::psql()),
+            _ => None,
+        }
+    }
+
+    /// The name of the preset style this is, or `None` if it isn't one of the presets.
+    ///
+    /// `TableStyle::double` is the same as `TableStyle::extended`, so it's named `"extended"`.
+    /// Likewise `TableStyle::ascii` is named `"simple"`
+    pub fn name(&self) -> Option<&'static str> {
+        [
+            "simple", "extended", "thin", "heavy", "rounded", "elegant", "blank", "empty",
+            "markdown", "psql",
+        ]
+        .iter()
+        .find(|name| TableStyle::from_name(name).as_ref() == Some(self))
+        .copied()
+    }
+
+    /// A copy of the style with heavier horizontal lines, such as for the separator above a summary row.
+    ///
+    /// Characters without a heavier version, such as those of `TableStyle::extended`, are kept
+    fn heavier(&self) -> TableStyle {
+        self.with_horizontal(match self.horizontal {
+            '-' => '=',
+            '─' => '━',
+            c => c,
+        })
+    }
+
+    /// A copy of the style that draws separators with another horizontal character.
+    ///
+    /// The junctions of thin vertical lines are swapped for ones that join heavy `━` or double `═` lines.
+    /// Junctions without such a version are kept
+    fn with_horizontal(&self, horizontal: char) -> TableStyle {
+        let junction = |c: char| match (horizontal, c) {
+            ('━', '├') => '┝',
+            ('━', '┼') => '┿',
+            ('━', '┤') => '┥',
+            ('━', '┬') => '┯',
+            ('━', '┴') => '┷',
+            ('═', '├') => '╞',
+            ('═', '┼') => '╪',
+            ('═', '┤') => '╡',
+            ('═', '┬') => '╤',
+            ('═', '┴') => '╧',
+            (_, c) => c,
+        };
+        TableStyle {
+            outer_left_vertical: junction(self.outer_left_vertical),
+            outer_right_vertical: junction(self.outer_right_vertical),
+            outer_bottom_horizontal: junction(self.outer_bottom_horizontal),
+            outer_top_horizontal: junction(self.outer_top_horizontal),
+            intersection: junction(self.intersection),
+            horizontal,
+            ..self.clone()
+        }
+    }
+
+    /// The graphemes of `horizontal_fill` that start at each display column of the pattern
+    fn fill_pattern(&self) -> Option<Vec<Option<&str>>> {
+        let fill = self.horizontal_fill.as_deref()?;
+        let mut pattern = Vec::new();
+        for grapheme in fill.graphemes(true) {
+            let width = string_width(grapheme);
+            if width > 0 {
+                pattern.push(Some(grapheme));
+                pattern.resize(pattern.len() + width - 1, None);
+            }
+        }
+        if pattern.is_empty() {
+            None
+        } else {
+            Some(pattern)
         }
     }
 
+    /// Replaces the horizontal characters of a separator with the `horizontal_fill` pattern
+    pub(crate) fn fill_horizontal(&self, separator: &str) -> String {
+        let pattern = match self.fill_pattern() {
+            Some(pattern) => pattern,
+            None => return separator.to_string(),
+        };
+        let mut out = String::with_capacity(separator.len());
+        let mut column = 0;
+        let mut chars = separator.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c != self.horizontal {
+                out.push(c);
+                column += c.width().unwrap_or(0);
+                continue;
+            }
+            let mut end = column + 1;
+            while chars.next_if_eq(&self.horizontal).is_some() {
+                end += 1;
+            }
+            while column < end {
+                match pattern[column % pattern.len()] {
+                    Some(grapheme) if column + string_width(grapheme) <= end => {
+                        out.push_str(grapheme);
+                        column += string_width(grapheme);
+                    }
+                    _ => {
+                        out.push(self.horizontal);
+                        column += 1;
+                    }
+                }
+            }
+        }
+        out
+    }
+
+    /// Reverses `fill_horizontal` so that a separator can be merged with another one
+    pub(crate) fn unfill_horizontal(&self, separator: &str) -> String {
+        let pattern = match self.fill_pattern() {
+            Some(pattern) => pattern,
+            None => return separator.to_string(),
+        };
+        let mut out = String::with_capacity(separator.len());
+        let mut column = 0;
+        for grapheme in separator.graphemes(true) {
+            let width = string_width(grapheme);
+            if pattern[column % pattern.len()] == Some(grapheme) {
+                out.push_str(&self.horizontal.to_string().repeat(width));
+            } else {
+                out.push_str(grapheme);
+            }
+            column += width;
+        }
+        out
+    }
+
     /// Returns the start character of a table style based on the
     /// vertical position of the row
     fn start_for_position(&self, pos: RowPosition) -> char {
@@ -377,24 +839,221 @@ impl TableStyle {
     /// Merges two intersecting characters based on the vertical position of a row.
     /// This is used to handle cases where one cell has a larger `col_span` value than the other
     fn merge_intersection_for_position(&self, top: char, bottom: char, pos: RowPosition) -> char {
+        if let Some(merge_fn) = self.merge_fn {
+            return merge_fn(top, bottom, pos);
+        }
         if (top == self.horizontal || top == self.outer_bottom_horizontal)
             && bottom == self.intersection
         {
-            return self.outer_top_horizontal;
+            self.outer_top_horizontal
         } else if (top == self.intersection || top == self.outer_top_horizontal)
             && bottom == self.horizontal
         {
-            return self.outer_bottom_horizontal;
+            self.outer_bottom_horizontal
         } else if top == self.outer_bottom_horizontal && bottom == self.horizontal {
-            return self.horizontal;
+            self.horizontal
         } else {
-            return self.intersect_for_position(pos);
+            self.intersect_for_position(pos)
+        }
+    }
+}
+
+/// Styles are equal when they use the same characters.
+///
+/// `merge_fn` isn't compared since the addresses of functions aren't guaranteed to be unique or stable
+impl PartialEq for TableStyle {
+    fn eq(&self, other: &Self) -> bool {
+        self.top_left_corner == other.top_left_corner
+            && self.top_right_corner == other.top_right_corner
+            && self.bottom_left_corner == other.bottom_left_corner
+            && self.bottom_right_corner == other.bottom_right_corner
+            && self.outer_left_vertical == other.outer_left_vertical
+            && self.outer_right_vertical == other.outer_right_vertical
+            && self.outer_bottom_horizontal == other.outer_bottom_horizontal
+            && self.outer_top_horizontal == other.outer_top_horizontal
+            && self.intersection == other.intersection
+            && self.vertical == other.vertical
+            && self.column_separator == other.column_separator
+            && self.horizontal == other.horizontal
+            && self.horizontal_fill == other.horizontal_fill
+    }
+}
+
+impl Eq for TableStyle {}
+
+/// Used to create table styles by overriding individual characters of an existing style
+#[derive(Clone, Debug)]
+pub struct TableStyleBuilder {
+    style: TableStyle,
+}
+
+impl TableStyleBuilder {
+    pub fn new() -> TableStyleBuilder {
+        TableStyleBuilder {
+            style: TableStyle::extended(),
+        }
+    }
+
+    /// Replaces all of the characters with those of another style. Defaults to `TableStyle::extended`
+    pub fn based_on(&mut self, style: TableStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    pub fn top_left_corner(&mut self, top_left_corner: char) -> &mut Self {
+        self.style.top_left_corner = top_left_corner;
+        self
+    }
+
+    pub fn top_right_corner(&mut self, top_right_corner: char) -> &mut Self {
+        self.style.top_right_corner = top_right_corner;
+        self
+    }
+
+    pub fn bottom_left_corner(&mut self, bottom_left_corner: char) -> &mut Self {
+        self.style.bottom_left_corner = bottom_left_corner;
+        self
+    }
+
+    pub fn bottom_right_corner(&mut self, bottom_right_corner: char) -> &mut Self {
+        self.style.bottom_right_corner = bottom_right_corner;
+        self
+    }
+
+    pub fn outer_left_vertical(&mut self, outer_left_vertical: char) -> &mut Self {
+        self.style.outer_left_vertical = outer_left_vertical;
+        self
+    }
+
+    pub fn outer_right_vertical(&mut self, outer_right_vertical: char) -> &mut Self {
+        self.style.outer_right_vertical = outer_right_vertical;
+        self
+    }
+
+    pub fn outer_bottom_horizontal(&mut self, outer_bottom_horizontal: char) -> &mut Self {
+        self.style.outer_bottom_horizontal = outer_bottom_horizontal;
+        self
+    }
+
+    pub fn outer_top_horizontal(&mut self, outer_top_horizontal: char) -> &mut Self {
+        self.style.outer_top_horizontal = outer_top_horizontal;
+        self
+    }
+
+    pub fn intersection(&mut self, intersection: char) -> &mut Self {
+        self.style.intersection = intersection;
+        self
+    }
+
+    pub fn vertical(&mut self, vertical: char) -> &mut Self {
+        // Column separators that match the vertical borders keep matching them
+        if self.style.column_separator == self.style.vertical {
+            self.style.column_separator = vertical;
+        }
+        self.style.vertical = vertical;
+        self
+    }
+
+    pub fn column_separator(&mut self, column_separator: char) -> &mut Self {
+        self.style.column_separator = column_separator;
+        self
+    }
+
+    pub fn horizontal(&mut self, horizontal: char) -> &mut Self {
+        self.style.horizontal = horizontal;
+        self
+    }
+
+    /// Sets the function used to choose the character where two separators with different column layouts meet
+    pub fn merge_fn(&mut self, merge_fn: fn(char, char, RowPosition) -> char) -> &mut Self {
+        self.style.merge_fn = Some(merge_fn);
+        self
+    }
+
+    /// Sets the characters repeated along horizontal lines instead of the horizontal character
+    pub fn horizontal_fill<T>(&mut self, horizontal_fill: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.style.horizontal_fill = Some(horizontal_fill.into());
+        self
+    }
+
+    /// Build a TableStyle using the current configuration
+    pub fn build(&self) -> TableStyle {
+        self.style.clone()
+    }
+}
+
+impl Default for TableStyleBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A value that can be displayed as a row of a table.
+///
+/// This can be derived for structs with the `derive` feature
+pub trait TableRow {
+    /// Creates a row containing the value's data
+    fn to_row(&self) -> Row;
+
+    /// Creates a header row naming each of the columns
+    fn header_row() -> Row;
+}
+
+/// The error returned when a table can't be created from JSON values
+#[cfg(feature = "serde_json")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum JsonError {
+    /// The value at the index is not a JSON object
+    NotAnObject(usize),
+}
+
+#[cfg(feature = "serde_json")]
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            JsonError::NotAnObject(index) => {
+                write!(f, "the value at index {} is not a JSON object", index)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl std::error::Error for JsonError {}
+
+/// The error returned when a table can't be parsed from Markdown
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParseError {
+    /// The input doesn't contain any rows
+    MissingHeader,
+    /// The header isn't followed by a delimiter row with a delimiter for each column, such as `| --- | :-: |`
+    MissingDelimiterRow,
+    /// The delimiter row is malformed at the column with the index
+    InvalidDelimiter(usize),
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseError::MissingHeader => write!(f, "the table doesn't have a header row"),
+            ParseError::MissingDelimiterRow => {
+                write!(f, "the header row isn't followed by a delimiter row")
+            }
+            ParseError::InvalidDelimiter(column) => {
+                write!(f, "the delimiter of column {} is invalid", column)
+            }
         }
     }
 }
 
+impl std::error::Error for ParseError {}
+
 /// A set of rows containing data
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Table {
     pub rows: Vec<Row>,
     pub style: TableStyle,
@@ -402,6 +1061,8 @@ pub struct Table {
     pub max_column_width: usize,
     /// The maximum widths of specific columns. Override max_column
     pub max_column_widths: HashMap<usize, usize>,
+    /// The default alignments of specific columns. Used by cells that don't have an alignment of their own
+    pub column_alignments: HashMap<usize, Alignment>,
     /// Whether or not to vertically separate rows in the table
     pub separate_rows: bool,
     /// Whether the table should have a top boarder.
@@ -409,18 +1070,153 @@ pub struct Table {
     pub has_top_boarder: bool,
     /// Whether the table should have a bottom boarder
     pub has_bottom_boarder: bool,
+    /// Whether the first row of the table is a header.
+    /// The separator below the header is always drawn, even if `separate_rows` is false
+    pub has_header: bool,
+    /// The horizontal character used for the separator below the header. Defaults to the style's `horizontal`.
+    /// With `━` or `═`, thin junctions such as `┼` are swapped for ones that join the heavier line
+    pub header_horizontal: Option<char>,
+    /// The default alignment of header cells that don't have an alignment of their own
+    pub header_alignment: Option<Alignment>,
+    /// The style used for the separators and vertical lines inside the table.
+    /// When `None`, `style` is used for the entire table
+    pub inner_style: Option<TableStyle>,
+    /// The number of blank lines above and below the content of each row
+    pub row_padding: usize,
+    /// Whether the table should have a left border or not
+    pub has_left_border: bool,
+    /// Whether the table should have a right border or not
+    pub has_right_border: bool,
+    /// Column widths stored by `freeze`. `None` when the widths need to be calculated
+    #[cfg_attr(feature = "serde", serde(skip))]
+    frozen_widths: Option<Vec<usize>>,
+    /// Text drawn in place of cells that have no content, such as cells created from `None`
+    pub none_placeholder: String,
+    /// The exact widths of specific columns, regardless of their content.
+    ///
+    /// Shorter content is padded and longer content is wrapped. These take precedence over `max_column_width`,
+    /// `max_column_widths` and the width cells need to be displayed. Columns past the last column of the table are added
+    pub fixed_column_widths: HashMap<usize, usize>,
+    /// The characters written at the end of each line
+    pub line_ending: LineEnding,
+    /// Whether East Asian characters of ambiguous width are two columns wide instead of one.
+    /// When true this overrides the setting of each cell
+    pub ambiguous_wide: bool,
+    /// Columns that are left out when the table is rendered. Cells spanning hidden columns span fewer columns instead
+    pub hidden_columns: HashSet<usize>,
+    /// Whether ansi escape sequences are removed from the content of the cells when rendering.
+    /// Enable this when the output isn't going to a terminal, such as when it's redirected to a file
+    pub strip_ansi: bool,
+    /// Whether rows where every cell is empty are left out when rendering. The header is always rendered
+    pub skip_empty_rows: bool,
+    /// A line centered above the table. It's wrapped when it's wider than the table
+    pub title: Option<String>,
+    /// A line centered below the table. It's wrapped when it's wider than the table
+    pub caption: Option<String>,
+    /// The style of the separator between the first and second rows, such as the line under a header.
+    /// When `None` it's drawn like the other separators
+    pub header_separator_style: Option<TableStyle>,
+    /// Whether columns are separated by vertical lines. When false the columns are separated by spaces
+    pub separate_columns: bool,
+    /// How control characters other than new lines and tabs in the content of the cells are rendered.
+    /// Terminals may move the cursor when they're printed, which breaks the layout of the table
+    pub control_chars: ControlChars,
+    /// The total width the table is padded to when its content is narrower. See `pad_to_width`
+    pub min_width: Option<usize>,
+    /// The column that is widened to pad the table to `min_width`. Defaults to the last column
+    pub padded_column: Option<usize>,
+    /// Whether the columns are laid out from right to left, with the first column on the right.
+    /// The left and right alignments of cells are swapped so content hugs the right edge by default
+    pub rtl: bool,
+    /// Whether spaces at the end of each line are removed when the right border isn't drawn or is blank,
+    /// such as with `TableStyle::blank`
+    pub trim_trailing_whitespace: bool,
+    /// The style of the separator above the last row, such as the line above a summary row.
+    /// It's always drawn, even if `separate_rows` is false. When `None` it's drawn like the other separators
+    pub footer_separator_style: Option<TableStyle>,
+    /// Whether the colors given to cells with `fg` and `bg` are left out when rendering.
+    /// Unlike `strip_ansi`, escape sequences in the content of the cells are kept. See `TableBuilder::respect_no_color`
+    pub no_color: bool,
 }
 
+/// Tables are equal when they have the same rows and settings. Whether their widths are frozen isn't compared
+impl PartialEq for Table {
+    fn eq(&self, other: &Self) -> bool {
+        self.rows == other.rows
+            && self.style == other.style
+            && self.max_column_width == other.max_column_width
+            && self.max_column_widths == other.max_column_widths
+            && self.column_alignments == other.column_alignments
+            && self.separate_rows == other.separate_rows
+            && self.has_top_boarder == other.has_top_boarder
+            && self.has_bottom_boarder == other.has_bottom_boarder
+            && self.has_header == other.has_header
+            && self.header_horizontal == other.header_horizontal
+            && self.header_alignment == other.header_alignment
+            && self.inner_style == other.inner_style
+            && self.row_padding == other.row_padding
+            && self.has_left_border == other.has_left_border
+            && self.has_right_border == other.has_right_border
+            && self.none_placeholder == other.none_placeholder
+            && self.fixed_column_widths == other.fixed_column_widths
+            && self.line_ending == other.line_ending
+            && self.ambiguous_wide == other.ambiguous_wide
+            && self.hidden_columns == other.hidden_columns
+            && self.strip_ansi == other.strip_ansi
+            && self.no_color == other.no_color
+            && self.skip_empty_rows == other.skip_empty_rows
+            && self.title == other.title
+            && self.caption == other.caption
+            && self.header_separator_style == other.header_separator_style
+            && self.footer_separator_style == other.footer_separator_style
+            && self.separate_columns == other.separate_columns
+            && self.control_chars == other.control_chars
+            && self.min_width == other.min_width
+            && self.padded_column == other.padded_column
+            && self.rtl == other.rtl
+            && self.trim_trailing_whitespace == other.trim_trailing_whitespace
+    }
+}
+
+impl Eq for Table {}
+
 impl Table {
     pub fn new() -> Table {
         Self {
             rows: Vec::new(),
             style: TableStyle::extended(),
-            max_column_width: std::usize::MAX,
+            max_column_width: usize::MAX,
             max_column_widths: HashMap::new(),
+            column_alignments: HashMap::new(),
             separate_rows: true,
             has_top_boarder: true,
             has_bottom_boarder: true,
+            has_header: false,
+            header_horizontal: None,
+            header_alignment: None,
+            inner_style: None,
+            row_padding: 0,
+            has_left_border: true,
+            has_right_border: true,
+            frozen_widths: None,
+            none_placeholder: String::new(),
+            fixed_column_widths: HashMap::new(),
+            line_ending: LineEnding::Lf,
+            ambiguous_wide: false,
+            hidden_columns: HashSet::new(),
+            strip_ansi: false,
+            skip_empty_rows: false,
+            title: None,
+            caption: None,
+            header_separator_style: None,
+            separate_columns: true,
+            control_chars: ControlChars::Keep,
+            min_width: None,
+            padded_column: None,
+            rtl: false,
+            trim_trailing_whitespace: false,
+            footer_separator_style: None,
+            no_color: false,
         }
     }
 
@@ -433,780 +1229,6382 @@ impl Table {
         Self {
             rows,
             style: TableStyle::extended(),
-            max_column_width: std::usize::MAX,
+            max_column_width: usize::MAX,
             max_column_widths: HashMap::new(),
+            column_alignments: HashMap::new(),
             separate_rows: true,
             has_top_boarder: true,
             has_bottom_boarder: true,
+            has_header: false,
+            header_horizontal: None,
+            header_alignment: None,
+            inner_style: None,
+            row_padding: 0,
+            has_left_border: true,
+            has_right_border: true,
+            frozen_widths: None,
+            none_placeholder: String::new(),
+            fixed_column_widths: HashMap::new(),
+            line_ending: LineEnding::Lf,
+            ambiguous_wide: false,
+            hidden_columns: HashSet::new(),
+            strip_ansi: false,
+            skip_empty_rows: false,
+            title: None,
+            caption: None,
+            header_separator_style: None,
+            separate_columns: true,
+            control_chars: ControlChars::Keep,
+            min_width: None,
+            padded_column: None,
+            rtl: false,
+            trim_trailing_whitespace: false,
+            footer_separator_style: None,
+            no_color: false,
         }
     }
 
-    pub fn max_column_width(&mut self, max_column_width: usize) -> &mut Self {
-        self.max_column_width = max_column_width;
-        self
+    /// Creates a table with a row for each value, below a header naming the columns
+    pub fn from_rows<T: TableRow>(rows: &[T]) -> Table {
+        Table::builder()
+            .header(T::header_row())
+            .rows(rows.iter().map(TableRow::to_row).collect())
+            .build()
     }
 
-    /// Set the max width of a particular column
-    pub fn set_max_width_for_column(&mut self, column_index: usize, width: usize) {
-        self.max_column_widths.insert(column_index, width);
+    /// Creates a table from values grouped by column, with the first value of each column in the first row.
+    ///
+    /// Columns shorter than the longest column are filled with empty cells
+    pub fn from_columns<T: ToString>(columns: Vec<Vec<T>>) -> Table {
+        let num_rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+        let mut rows: Vec<Row> = (0..num_rows).map(|_| Row::empty()).collect();
+        for column in columns {
+            let mut values = column.into_iter();
+            for row in rows.iter_mut() {
+                row.add_cell(match values.next() {
+                    Some(value) => TableCell::new(value),
+                    None => TableCell::new(""),
+                });
+            }
+        }
+        Table::builder().rows(rows).build()
     }
 
-    /// Set the max widths of specific columns
-    pub fn set_max_column_widths(&mut self, index_width_pairs: Vec<(usize, usize)>) {
-        for pair in index_width_pairs {
-            self.max_column_widths.insert(pair.0, pair.1);
+    /// Creates a table with a row for each JSON object.
+    ///
+    /// Rows are in the same order as the values. The header contains every key in the order they are first seen,
+    /// going through the keys of each object in the order `serde_json` stores them. That is alphabetical unless
+    /// its `preserve_order` feature is enabled. Keys missing from an object are left empty, and nested objects
+    /// and arrays are shown as compact JSON
+    #[cfg(feature = "serde_json")]
+    pub fn from_json(values: &[serde_json::Value]) -> Result<Table, JsonError> {
+        let mut objects = Vec::with_capacity(values.len());
+        let mut keys: Vec<&String> = Vec::new();
+        for (index, value) in values.iter().enumerate() {
+            let object = value.as_object().ok_or(JsonError::NotAnObject(index))?;
+            for key in object.keys() {
+                if !keys.contains(&key) {
+                    keys.push(key);
+                }
+            }
+            objects.push(object);
         }
+
+        let rows = objects
+            .into_iter()
+            .map(|object| {
+                Row::new(keys.iter().map(|key| match object.get(*key) {
+                    None => TableCell::new(""),
+                    Some(serde_json::Value::String(string)) => TableCell::new(string),
+                    Some(serde_json::Value::Number(number)) => TableCell::number(number),
+                    Some(value) => TableCell::new(value),
+                }))
+            })
+            .collect();
+
+        Ok(Table::builder().header(Row::new(keys)).rows(rows).build())
     }
 
-    /// Simply adds a row to the rows Vec
-    pub fn add_row(&mut self, row: Row) {
-        self.rows.push(row);
+    /// Parses a GitHub Flavored Markdown table.
+    ///
+    /// The first row becomes the header and the delimiter row below it sets the alignment of each column.
+    /// Rows are padded with empty cells or cut off to have as many cells as the header.
+    /// Escaped pipes (`\|`) are unescaped and `<br>` tags become line breaks
+    pub fn from_markdown(input: &str) -> Result<Table, ParseError> {
+        let mut lines = input.lines().map(str::trim).filter(|line| !line.is_empty());
+        let header = split_markdown_row(lines.next().ok_or(ParseError::MissingHeader)?);
+        let delimiters = split_markdown_row(lines.next().ok_or(ParseError::MissingDelimiterRow)?);
+        if delimiters.len() != header.len() {
+            return Err(ParseError::MissingDelimiterRow);
+        }
+
+        let mut builder = Table::builder();
+        for (column, delimiter) in delimiters.iter().enumerate() {
+            let dashes = delimiter.trim_start_matches(':').trim_end_matches(':');
+            if dashes.is_empty() || !dashes.chars().all(|c| c == '-') {
+                return Err(ParseError::InvalidDelimiter(column));
+            }
+            let alignment = match (delimiter.starts_with(':'), delimiter.ends_with(':')) {
+                (true, true) => Alignment::Center,
+                (true, false) => Alignment::Left,
+                (false, true) => Alignment::Right,
+                (false, false) => continue,
+            };
+            builder.column_alignment(column, alignment);
+        }
+
+        let rows = lines
+            .map(|line| {
+                let mut cells = split_markdown_row(line);
+                cells.resize(header.len(), String::new());
+                Row::new(cells)
+            })
+            .collect();
+
+        Ok(builder.header(Row::new(header)).rows(rows).build())
     }
 
-    /// Does all of the calculations to reformat the row based on it's current
-    /// state and returns the result as a `String`
-    pub fn render(&self) -> String {
-        let mut print_buffer = String::new();
-        let max_widths = self.calculate_max_column_widths();
-        let mut previous_separator = None;
-        if !self.rows.is_empty() {
-            for i in 0..self.rows.len() {
-                let row_pos = if i == 0 {
-                    RowPosition::First
-                } else {
-                    RowPosition::Mid
-                };
+    pub fn max_column_width(&mut self, max_column_width: usize) -> &mut Self {
+        self.frozen_widths = None;
+        self.max_column_width = max_column_width;
+        self
+    }
 
-                let separator = self.rows[i].gen_separator(
-                    &max_widths,
-                    &self.style,
-                    row_pos,
-                    previous_separator.clone(),
-                );
+    /// Set the max width of a particular column
+    pub fn set_max_width_for_column(&mut self, column_index: usize, width: usize) {
+        self.frozen_widths = None;
+        self.max_column_widths.insert(column_index, width);
+    }
 
-                previous_separator = Some(separator.clone());
+    /// Set the max widths of specific columns
+    pub fn set_max_column_widths(&mut self, index_width_pairs: Vec<(usize, usize)>) {
+        self.frozen_widths = None;
+        for pair in index_width_pairs {
+            self.max_column_widths.insert(pair.0, pair.1);
+        }
+    }
 
-                if self.rows[i].has_separator
-                    && ((i == 0 && self.has_top_boarder) || i != 0 && self.separate_rows)
-                {
-                    Table::buffer_line(&mut print_buffer, &separator);
-                }
+    /// Shrinks the columns so that the rendered table fits within the width of the terminal.
+    ///
+    /// Does nothing if the width of the terminal can't be detected
+    #[cfg(feature = "terminal")]
+    pub fn fit_to_terminal(&mut self) -> &mut Self {
+        if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+            self.fit_to_width(width as usize);
+        }
+        self
+    }
+
+    /// Widens the columns so that the rendered table fills the width of the terminal.
+    ///
+    /// Does nothing if the width of the terminal can't be detected
+    #[cfg(feature = "terminal")]
+    pub fn expand_to_terminal(&mut self) -> &mut Self {
+        if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+            self.expand_to_width(width as usize);
+        }
+        self
+    }
 
-                Table::buffer_line(
-                    &mut print_buffer,
-                    &self.rows[i].format(&max_widths, &self.style),
-                );
+    /// Sets the max widths of the columns so that the rendered table is no wider than `total`.
+    ///
+    /// The widest columns are shrunk first. Columns are never shrunk below the width
+    /// required to display their cells, so the table may still be wider than `total`
+    pub fn fit_to_width(&mut self, total: usize) -> &mut Self {
+        self.frozen_widths = None;
+        let rows = self.grid_rows(&self.place_cells(), None);
+        let mut widths = self.calculate_max_column_widths(&rows);
+        let mut min_widths = vec![0; widths.len()];
+        for row in &rows {
+            for (i, (_, min_width)) in row.split_column_widths().into_iter().enumerate() {
+                min_widths[i] = max(min_widths[i], min_width);
             }
-            if self.has_bottom_boarder {
-                let separator = self.rows.last().unwrap().gen_separator(
-                    &max_widths,
-                    &self.style,
-                    RowPosition::Last,
-                    None,
-                );
-                Table::buffer_line(&mut print_buffer, &separator);
+        }
+
+        // Columns with fixed widths are never shrunk
+        for (&column, &width) in &self.fixed_column_widths {
+            min_widths[column] = width;
+        }
+
+        // Every column has a vertical character to its left plus one at the end of the table
+        let borders = widths.len() + 1;
+        while widths.iter().sum::<usize>() + borders > total {
+            let widest = (0..widths.len())
+                .filter(|i| widths[*i] > min_widths[*i])
+                .max_by_key(|i| (widths[*i], std::cmp::Reverse(*i)));
+            match widest {
+                Some(i) => widths[i] -= 1,
+                None => break,
             }
         }
-        return print_buffer;
+
+        for (i, width) in widths.into_iter().enumerate() {
+            self.max_column_widths.insert(i, width);
+        }
+        self
     }
 
-    /// Calculates the maximum width for each column.
-    /// If a cell has a column span greater than 1, then the width
-    /// of it's contents are divided by the column span, otherwise the cell
-    /// would use more space than it needed.
-    fn calculate_max_column_widths(&self) -> Vec<usize> {
-        let mut num_columns = 0;
+    /// Sets the widths of the columns so that the rendered table is `total` wide, sharing the extra space between
+    /// the columns in proportion to the width of their content.
+    ///
+    /// Columns aren't widened past their width in `max_column_widths`, so a column can be left as it is by setting it
+    /// to the current width of the column. Nothing is widened if the table is already at least `total` wide
+    pub fn expand_to_width(&mut self, total: usize) -> &mut Self {
+        self.frozen_widths = None;
+        let content_widths = self.column_widths();
+        let mut widths = content_widths.clone();
+        let max_widths: Vec<usize> = (0..widths.len())
+            .map(|i| match self.fixed_column_widths.get(&i) {
+                Some(_) => widths[i],
+                None => max(
+                    widths[i],
+                    *self.max_column_widths.get(&i).unwrap_or(&usize::MAX),
+                ),
+            })
+            .collect();
 
-        for row in &self.rows {
-            num_columns = max(row.num_columns(), num_columns);
+        let mut extra = total.saturating_sub(self.rendered_width(&widths));
+        while extra > 0 {
+            let growing: Vec<usize> = (0..widths.len())
+                .filter(|i| widths[*i] < max_widths[*i])
+                .collect();
+            if growing.is_empty() {
+                break;
+            }
+
+            // Columns that reach their max width stop growing and the rest share what's left
+            let content_total: usize = growing.iter().map(|i| content_widths[*i]).sum();
+            let mut given = 0;
+            for &i in &growing {
+                // Columns without content share the space evenly
+                let share = (extra * content_widths[i])
+                    .checked_div(content_total)
+                    .unwrap_or(extra / growing.len());
+                let share = min(share, max_widths[i] - widths[i]);
+                widths[i] += share;
+                given += share;
+            }
+
+            // The space lost to rounding goes to the widest columns one at a time
+            if given == 0 {
+                let widest = growing
+                    .into_iter()
+                    .max_by_key(|i| (content_widths[*i], std::cmp::Reverse(*i)))
+                    .unwrap();
+                widths[widest] += 1;
+                given = 1;
+            }
+            extra -= given;
         }
-        let mut max_widths: Vec<usize> = vec![0; num_columns];
-        let mut min_widths: Vec<usize> = vec![0; num_columns];
-        for row in &self.rows {
-            let column_widths = row.split_column_widths();
-            for i in 0..column_widths.len() {
-                min_widths[i] = max(min_widths[i], column_widths[i].1);
-                let mut max_width = *self
-                    .max_column_widths
-                    .get(&i)
-                    .unwrap_or(&self.max_column_width);
-                max_width = max(min_widths[i] as usize, max_width);
-                max_widths[i] = min(max_width, max(max_widths[i], column_widths[i].0 as usize));
+
+        for (i, width) in widths.into_iter().enumerate() {
+            self.fixed_column_widths.insert(i, width);
+        }
+        self
+    }
+
+    /// Widens the last column, or `padded_column` if it's set, so that the rendered table is `total` wide.
+    ///
+    /// The columns are sized to their content first. Nothing is widened if the table is already
+    /// at least `total` wide
+    pub fn pad_to_width(&mut self, total: usize) -> &mut Self {
+        self.frozen_widths = None;
+        self.min_width = Some(total);
+        self
+    }
+
+    /// Sets the header of the table, replacing the current header if there is one
+    pub fn set_header(&mut self, header: Row) {
+        self.frozen_widths = None;
+        if self.has_header && !self.rows.is_empty() {
+            self.rows[0] = header;
+        } else {
+            self.rows.insert(0, header);
+            self.has_header = true;
+        }
+    }
+
+    /// Simply adds a row to the rows Vec
+    pub fn add_row(&mut self, row: Row) {
+        self.frozen_widths = None;
+        self.rows.push(row);
+    }
+
+    /// Inserts a row at an index, shifting the rows after it down.
+    ///
+    /// Indices past the end of the table add the row to the end. Indices include the header if there is one,
+    /// but rows are never inserted above it, so index 0 inserts the row right below the header
+    pub fn insert_row(&mut self, index: usize, row: Row) {
+        self.frozen_widths = None;
+        let first = if self.has_header { 1 } else { 0 };
+        self.rows
+            .insert(min(max(index, first), self.rows.len()), row);
+    }
+
+    /// Removes and returns the row at an index, or `None` if there is no row at the index.
+    ///
+    /// Indices include the header if there is one, so index 0 removes the header and the table no longer has one
+    pub fn remove_row(&mut self, index: usize) -> Option<Row> {
+        self.frozen_widths = None;
+        if index >= self.rows.len() {
+            return None;
+        }
+        if index == 0 {
+            self.has_header = false;
+        }
+        Some(self.rows.remove(index))
+    }
+
+    /// Calls `f` with the row index, column index and content of every cell, replacing the cell
+    /// with the one returned by `f` when it returns `Some`.
+    ///
+    /// Row indices include the header if there is one. Column indices take cells spanning
+    /// multiple columns or rows into account, so they are the columns the cells are rendered in
+    pub fn format_cells<F>(&mut self, f: F)
+    where
+        F: Fn(usize, usize, &str) -> Option<TableCell>,
+    {
+        self.frozen_widths = None;
+        let grid = self.place_cells();
+        for (row_idx, slots) in grid.rows.iter().enumerate() {
+            let mut column = 0;
+            for slot in slots {
+                let (cell_idx, col_span) = match slot {
+                    GridSlot::Cell(cell_idx, col_span) => (Some(*cell_idx), *col_span),
+                    GridSlot::RowSpan(span_idx) => {
+                        let span = &grid.row_spans[*span_idx];
+                        let cell_idx = if span.row == row_idx {
+                            Some(span.cell)
+                        } else {
+                            None
+                        };
+                        (cell_idx, span.col_span)
+                    }
+                    GridSlot::Empty => (None, 1),
+                };
+                if let Some(cell_idx) = cell_idx {
+                    let cell = &mut self.rows[row_idx].cells[cell_idx];
+                    if let Some(replacement) = f(row_idx, column, &cell.data) {
+                        *cell = replacement;
+                    }
+                }
+                column += col_span;
+            }
+        }
+    }
+
+    /// Adds a column before the first column that numbers the rows, starting at 1.
+    ///
+    /// The header, if there is one, contains `header` instead of a number. Settings for specific
+    /// columns, such as their max widths and alignments, move with their columns
+    pub fn with_index_column(&mut self, header: &str) {
+        self.frozen_widths = None;
+        let header_len = if self.has_header {
+            min(1, self.rows.len())
+        } else {
+            0
+        };
+        let cells = (0..self.rows.len()).map(|i| {
+            if i < header_len {
+                TableCell::new(header)
+            } else {
+                TableCell::from(i + 1 - header_len)
             }
+        });
+        self.prepend_column(cells.collect());
+    }
+
+    /// Adds a cell to the start of each row, moving the settings for specific columns along with their columns
+    fn prepend_column(&mut self, cells: Vec<TableCell>) {
+        for (row, cell) in self.rows.iter_mut().zip(cells) {
+            row.cells.insert(0, cell);
         }
 
-        // Here we are dealing with the case where we have a cell that is center
-        // aligned but the max_width doesn't allow for even padding on either side
-        for row in &self.rows {
-            let mut col_index = 0;
-            for cell in row.cells.iter() {
-                let mut total_col_width = 0;
-                for i in col_index..col_index + cell.col_span {
-                    total_col_width += max_widths[i];
+        fn shift<T>(map: &mut HashMap<usize, T>) {
+            *map = map
+                .drain()
+                .map(|(column, value)| (column + 1, value))
+                .collect();
+        }
+        shift(&mut self.max_column_widths);
+        shift(&mut self.fixed_column_widths);
+        shift(&mut self.column_alignments);
+        self.hidden_columns = self
+            .hidden_columns
+            .iter()
+            .map(|column| column + 1)
+            .collect();
+        self.padded_column = self.padded_column.map(|column| column + 1);
+    }
+
+    /// Compares the table to an older version of it, creating a copy of the table that highlights what changed.
+    ///
+    /// Rows and cells are matched by their indices. Cells with content that differs from the cell at the same
+    /// position in `other` are yellow. A column is added before the first column that marks rows missing from
+    /// `other` with a green `+`, and rows of `other` missing from this table are added in red and marked with `-`
+    pub fn diff(&self, other: &Table) -> Table {
+        let mut table = self.unfrozen();
+        let mut markers = Vec::with_capacity(max(self.rows.len(), other.rows.len()));
+        for (i, row) in table.rows.iter_mut().enumerate() {
+            match other.rows.get(i) {
+                Some(old) => {
+                    for (j, cell) in row.cells.iter_mut().enumerate() {
+                        if old.cells.get(j).map(|old| &old.data) != Some(&cell.data) {
+                            cell.fg = Some(Color::Yellow);
+                        }
+                    }
+                    markers.push(TableCell::new(""));
                 }
-                if cell.width() != total_col_width
-                    && cell.alignment == Alignment::Center
-                    && total_col_width as f32 % 2.0 <= 0.001
-                {
-                    let mut max_col_width = self.max_column_width;
-                    if let Some(specific_width) = self.max_column_widths.get(&col_index) {
-                        max_col_width = *specific_width;
+                None => {
+                    for cell in row.cells.iter_mut() {
+                        cell.fg = Some(Color::Green);
                     }
+                    markers.push(TableCell::builder("+").fg(Color::Green).build());
+                }
+            }
+        }
+        for row in other.rows.iter().skip(self.rows.len()) {
+            let mut row = row.clone();
+            for cell in row.cells.iter_mut() {
+                cell.fg = Some(Color::Red);
+            }
+            table.rows.push(row);
+            markers.push(TableCell::builder("-").fg(Color::Red).build());
+        }
+        table.prepend_column(markers);
+        table
+    }
 
-                    if max_widths[col_index] < max_col_width {
-                        max_widths[col_index] += 1;
+    /// Swaps two rows. Indices include the header if there is one.
+    ///
+    /// Returns `false` without changing the table if either index is past the last row
+    pub fn swap_rows(&mut self, a: usize, b: usize) -> bool {
+        if a >= self.rows.len() || b >= self.rows.len() {
+            return false;
+        }
+        self.frozen_widths = None;
+        self.rows.swap(a, b);
+        true
+    }
+
+    /// Swaps two columns, moving the settings for specific columns along with their columns.
+    ///
+    /// Rows where either column is part of a cell spanning multiple columns or rows, or that don't reach
+    /// both columns, are left unchanged. Returns `false` without changing the table if either index is
+    /// past the last column
+    pub fn swap_columns(&mut self, a: usize, b: usize) -> bool {
+        let column_count = self.column_count();
+        if a >= column_count || b >= column_count {
+            return false;
+        }
+        self.frozen_widths = None;
+
+        let grid = self.place_cells();
+        for (row_idx, slots) in grid.rows.iter().enumerate() {
+            // The index of the cell at each column that only takes up that column
+            let mut single_cells = Vec::new();
+            let mut column = 0;
+            for slot in slots {
+                match slot {
+                    GridSlot::Cell(cell_idx, col_span) => {
+                        if *col_span == 1 {
+                            single_cells.push((column, *cell_idx));
+                        }
+                        column += col_span;
                     }
+                    GridSlot::RowSpan(span_idx) => column += grid.row_spans[*span_idx].col_span,
+                    GridSlot::Empty => column += 1,
                 }
-                if cell.col_span > 1 {
-                    col_index += cell.col_span - 1;
-                } else {
-                    col_index += 1;
-                }
+            }
+            let cell_at = |column: usize| {
+                single_cells
+                    .iter()
+                    .find(|(c, _)| *c == column)
+                    .map(|(_, cell_idx)| *cell_idx)
+            };
+            if let (Some(x), Some(y)) = (cell_at(a), cell_at(b)) {
+                self.rows[row_idx].cells.swap(x, y);
             }
         }
 
-        return max_widths;
+        fn swap<T>(map: &mut HashMap<usize, T>, a: usize, b: usize) {
+            let value_a = map.remove(&a);
+            let value_b = map.remove(&b);
+            if let Some(value) = value_a {
+                map.insert(b, value);
+            }
+            if let Some(value) = value_b {
+                map.insert(a, value);
+            }
+        }
+        swap(&mut self.max_column_widths, a, b);
+        swap(&mut self.fixed_column_widths, a, b);
+        swap(&mut self.column_alignments, a, b);
+        if self.hidden_columns.contains(&a) != self.hidden_columns.contains(&b) {
+            for column in [a, b] {
+                if !self.hidden_columns.remove(&column) {
+                    self.hidden_columns.insert(column);
+                }
+            }
+        }
+        self.padded_column = self.padded_column.map(|column| match column {
+            column if column == a => b,
+            column if column == b => a,
+            column => column,
+        });
+        true
     }
 
-    /// Helper method for adding a line to a string buffer
-    fn buffer_line(buffer: &mut String, line: &str) {
-        buffer.push_str(format!("{}\n", line).as_str());
+    /// Reverses the order of the rows. The header stays at the top
+    pub fn reverse_rows(&mut self) {
+        self.frozen_widths = None;
+        let header_len = if self.has_header {
+            min(1, self.rows.len())
+        } else {
+            0
+        };
+        self.rows[header_len..].reverse();
     }
-}
 
-impl Default for Table {
-    fn default() -> Self {
-        return Table::new();
+    /// Removes rows that have the same cell content as the row above them. The header is always kept
+    pub fn dedup_rows(&mut self) {
+        self.frozen_widths = None;
+        let header_len = if self.has_header {
+            min(1, self.rows.len())
+        } else {
+            0
+        };
+        let rows = self.rows.split_off(header_len);
+        for row in rows {
+            let duplicate = match self.rows[header_len..].last() {
+                Some(last) => {
+                    last.cells.len() == row.cells.len()
+                        && last
+                            .cells
+                            .iter()
+                            .zip(&row.cells)
+                            .all(|(a, b)| a.data == b.data)
+                }
+                None => false,
+            };
+            if !duplicate {
+                self.rows.push(row);
+            }
+        }
     }
-}
 
-impl ToString for Table {
-    fn to_string(&self) -> String {
-        return self.render();
+    /// Moves all of the rows of `other` to the end of the table.
+    ///
+    /// The rows are rendered with the same column widths as the rest of the table, so the columns of both
+    /// tables line up. A header in `other` becomes a regular row, and the settings of `other` are not kept.
+    ///
+    /// Tables with a different number of columns can't be lined up, so when both tables have rows and their
+    /// column counts differ, nothing is appended and `other` is returned as the error
+    #[allow(clippy::result_large_err)]
+    pub fn append(&mut self, other: Table) -> Result<(), Table> {
+        if !self.rows.is_empty()
+            && !other.rows.is_empty()
+            && self.column_count() != other.column_count()
+        {
+            return Err(other);
+        }
+        self.frozen_widths = None;
+        if self.rows.is_empty() {
+            self.has_header = other.has_header;
+        }
+        self.rows.extend(other.rows);
+        Ok(())
     }
-}
 
-/// Used to create non-mutable tables
-#[derive(Clone, Debug)]
-pub struct TableBuilder {
-    rows: Vec<Row>,
-    style: TableStyle,
-    max_column_width: usize,
-    max_column_widths: HashMap<usize, usize>,
-    separate_rows: bool,
-    has_top_boarder: bool,
-    has_bottom_boarder: bool,
-}
+    /// Creates a table that draws this table and `other` next to each other, separated by two spaces.
+    ///
+    /// Both tables are rendered with their own settings and borders. When one table is shorter than the other,
+    /// the lines below it are filled with spaces
+    pub fn beside(&self, other: &Table) -> Table {
+        // Verbatim cells aren't padded, so the gap is added to the end of each line of the left table
+        let left: Vec<String> = self
+            .render()
+            .lines()
+            .map(|line| format!("{}  ", line))
+            .collect();
+        let left = TableCell::builder(left.join("\n")).verbatim(true).build();
+        let right = TableCell::builder(other.render().trim_end_matches('\n'))
+            .verbatim(true)
+            .build();
+        Table::builder()
+            .rows(vec![Row::new(vec![left, right])])
+            .style(TableStyle::blank())
+            .has_top_boarder(false)
+            .has_bottom_boarder(false)
+            .has_left_border(false)
+            .has_right_border(false)
+            .build()
+    }
 
-impl TableBuilder {
-    pub fn new() -> TableBuilder {
-        TableBuilder {
-            rows: Vec::new(),
-            style: TableStyle::extended(),
-            max_column_width: std::usize::MAX,
-            max_column_widths: HashMap::new(),
-            separate_rows: true,
-            has_top_boarder: true,
-            has_bottom_boarder: true,
+    /// Adds a row summarizing each column, such as a row of totals, to the end of the table.
+    ///
+    /// `summarize` is called with the index of each column and the content of its cells, excluding the header.
+    /// It returns the content of the column's summary cell, or `None` to leave the cell empty. Summary cells are
+    /// aligned like the last cell in their column.
+    ///
+    /// Unless the table already has a `footer_separator_style`, the separator above the summary row is drawn
+    /// with heavier lines where the style has them, such as `=` instead of `-` for `TableStyle::simple`
+    pub fn add_summary_row<F>(&mut self, summarize: F)
+    where
+        F: Fn(usize, &[&str]) -> Option<String>,
+    {
+        let skip = if self.has_header { 1 } else { 0 };
+        let cells: Vec<TableCell> = (0..self.column_count())
+            .map(|column| {
+                let cells: Vec<&TableCell> = self
+                    .rows
+                    .iter()
+                    .skip(skip)
+                    .filter_map(|row| row.cell_at_column(column))
+                    .collect();
+                let values: Vec<&str> = cells.iter().map(|cell| cell.data.as_str()).collect();
+                let mut cell = TableCell::new(summarize(column, &values).unwrap_or_default());
+                cell.set_own_alignment(cells.last().and_then(|cell| cell.own_alignment()));
+                cell
+            })
+            .collect();
+        self.add_row(Row::new(cells));
+        if self.footer_separator_style.is_none() {
+            let style = self.inner_style.as_ref().unwrap_or(&self.style);
+            self.footer_separator_style = Some(style.heavier());
         }
     }
 
-    pub fn rows(&mut self, rows: Vec<Row>) -> &mut Self {
-        self.rows = rows;
-        self
+    /// Creates a copy of the table containing at most `max_rows` rows below the header.
+    ///
+    /// The rows that don't fit are replaced by a single row saying how many rows were left out.
+    /// The header, if there is one, is always kept
+    pub fn with_max_rows(&self, max_rows: usize) -> Table {
+        let header_len = if self.has_header {
+            min(1, self.rows.len())
+        } else {
+            0
+        };
+        let kept = min(header_len + max_rows, self.rows.len());
+        let remaining = self.rows.len() - kept;
+        if remaining == 0 {
+            return self.unfrozen();
+        }
+
+        let mut rows: Vec<Row> = self.rows[..kept].to_vec();
+        // Cells can't span down into the rows that were left out
+        for (i, row) in rows.iter_mut().enumerate() {
+            for cell in &mut row.cells {
+                cell.row_span = min(cell.row_span, kept - i);
+            }
+        }
+        let message = if remaining == 1 {
+            "… 1 more row".to_string()
+        } else {
+            format!("… {} more rows", remaining)
+        };
+        rows.push(Row::new(vec![TableCell::builder(message)
+            .col_span(max(self.column_count(), 1))
+            .alignment(Alignment::Center)
+            .build()]));
+
+        Table {
+            rows,
+            ..self.unfrozen()
+        }
     }
 
-    pub fn style(&mut self, style: TableStyle) -> &mut Self {
-        self.style = style;
-        self
+    /// Removes all of the rows from the table, including the header
+    pub fn clear(&mut self) {
+        self.frozen_widths = None;
+        self.rows.clear();
+        self.has_header = false;
     }
 
-    /// The maximum width of all columns. Overridden by values in column_widths. Defaults to `std::usize::max`
-    pub fn max_column_width(&mut self, max_column_width: usize) -> &mut Self {
-        self.max_column_width = max_column_width;
-        self
+    /// The borders and separators that are drawn, taken from `has_top_boarder`, `has_bottom_boarder`,
+    /// `has_left_border`, `has_right_border`, `separate_rows` and `separate_columns`
+    pub fn borders(&self) -> Borders {
+        let mut borders = Borders::NONE;
+        let flags = [
+            (self.has_top_boarder, Borders::TOP),
+            (self.has_bottom_boarder, Borders::BOTTOM),
+            (self.has_left_border, Borders::LEFT),
+            (self.has_right_border, Borders::RIGHT),
+            (self.separate_rows, Borders::INNER_HORIZONTAL),
+            (self.separate_columns, Borders::INNER_VERTICAL),
+        ];
+        for (enabled, border) in flags.iter() {
+            if *enabled {
+                borders |= *border;
+            }
+        }
+        borders
     }
 
-    /// The maximum widths of specific columns. Override max_column
-    pub fn max_column_widths(&mut self, max_column_widths: HashMap<usize, usize>) -> &mut Self {
-        self.max_column_widths = max_column_widths;
-        self
+    /// Sets which borders and separators are drawn. The separator below the header is always drawn
+    pub fn set_borders(&mut self, borders: Borders) {
+        self.has_top_boarder = borders.contains(Borders::TOP);
+        self.has_bottom_boarder = borders.contains(Borders::BOTTOM);
+        self.has_left_border = borders.contains(Borders::LEFT);
+        self.has_right_border = borders.contains(Borders::RIGHT);
+        self.separate_rows = borders.contains(Borders::INNER_HORIZONTAL);
+        self.separate_columns = borders.contains(Borders::INNER_VERTICAL);
     }
 
-    /// Whether or not to vertically separate rows in the table
-    pub fn separate_rows(&mut self, separate_rows: bool) -> &mut Self {
-        self.separate_rows = separate_rows;
-        self
+    /// Number of columns in the table, which is the number of columns in its widest row.
+    ///
+    /// Cells spanning past the last column any other cell starts in are cut short, so they don't add columns
+    pub fn column_count(&self) -> usize {
+        self.place_cells().columns
     }
 
-    /// Whether the table should have a top boarder.
-    /// Setting `has_separator` to false on the first row will have the same effect as setting this to false
-    pub fn has_top_boarder(&mut self, has_top_boarder: bool) -> &mut Self {
-        self.has_top_boarder = has_top_boarder;
-        self
+    /// The cells of the table laid out on a grid of rows and columns.
+    ///
+    /// A cell appears in the first row and column it covers. The other positions a cell spans,
+    /// along with the positions past the end of a short row, are `None`
+    pub fn grid(&self) -> Vec<Vec<Option<&TableCell>>> {
+        let grid = self.place_cells();
+        let mut columns = self.column_count();
+        let mut rows: Vec<Vec<Option<&TableCell>>> = grid
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, slots)| {
+                let mut cells = Vec::with_capacity(columns);
+                for slot in slots {
+                    let (cell, col_span) = match slot {
+                        GridSlot::Cell(cell_idx, col_span) => {
+                            (Some(&self.rows[row_idx].cells[*cell_idx]), *col_span)
+                        }
+                        GridSlot::RowSpan(span_idx) => {
+                            let span = &grid.row_spans[*span_idx];
+                            let cell = &self.rows[span.row].cells[span.cell];
+                            if span.row == row_idx {
+                                (Some(cell), span.col_span)
+                            } else {
+                                (None, span.col_span)
+                            }
+                        }
+                        GridSlot::Empty => (None, 1),
+                    };
+                    cells.push(cell);
+                    cells.extend((1..col_span).map(|_| None));
+                }
+                columns = columns.max(cells.len());
+                cells
+            })
+            .collect();
+
+        // Cells spanning down from previous rows can push a row past the widest row of cells
+        for row in rows.iter_mut() {
+            row.resize(columns, None);
+        }
+        rows
     }
 
-    /// Whether the table should have a bottom boarder
-    pub fn has_bottom_boarder(&mut self, has_bottom_boarder: bool) -> &mut Self {
-        self.has_bottom_boarder = has_bottom_boarder;
-        self
+    /// Number of rows in the table, including the header if there is one
+    pub fn row_count(&self) -> usize {
+        self.rows.len()
     }
 
-    /// Build a Table using the current configuration
-    pub fn build(&self) -> Table {
+    /// Creates a copy of the table with its rows and columns swapped.
+    ///
+    /// Cells are treated as if they only span a single column and row. Rows with fewer cells
+    /// than the widest row are filled with empty cells. The style and borders are kept, but the
+    /// header and any column specific settings are not since the columns no longer match
+    pub fn transpose(&self) -> Table {
+        let columns = self
+            .rows
+            .iter()
+            .map(|row| row.cells.len())
+            .max()
+            .unwrap_or(0);
+        let rows = (0..columns)
+            .map(|column| {
+                Row::new(self.rows.iter().map(|row| match row.cells.get(column) {
+                    Some(cell) => TableCell {
+                        col_span: 1,
+                        row_span: 1,
+                        ..cell.clone()
+                    },
+                    None => TableCell::new(""),
+                }))
+            })
+            .collect();
+
         Table {
-            rows: self.rows.clone(),
-            style: self.style,
-            max_column_width: self.max_column_width,
-            max_column_widths: self.max_column_widths.clone(),
-            separate_rows: self.separate_rows,
-            has_top_boarder: self.has_top_boarder,
-            has_bottom_boarder: self.has_bottom_boarder,
+            rows,
+            max_column_widths: HashMap::new(),
+            column_alignments: HashMap::new(),
+            has_header: false,
+            ..self.unfrozen()
         }
     }
-}
 
-impl Default for TableBuilder {
-    fn default() -> Self {
-        Self::new()
+    /// Does all of the calculations to reformat the row based on it's current
+    /// state and returns the result as a `String`
+    pub fn render(&self) -> String {
+        let mut buf = String::new();
+        self.render_into(&mut buf);
+        buf
     }
-}
 
-#[cfg(test)]
-mod test {
-    use crate::row::Row;
-    use crate::table_cell::{Alignment, TableCell};
-    use crate::Table;
-    use crate::TableBuilder;
-    use crate::TableStyle;
-    use pretty_assertions::assert_eq;
+    /// Renders the table as a list of lines without their line endings.
+    ///
+    /// The lines are collected as they are formatted rather than split from the rendered table.
+    /// Both `\n` and `\r\n` are removed, so joining the lines with the table's line ending gives `render`
+    pub fn to_lines(&self) -> Vec<String> {
+        if let Some(table) = self.visible_table() {
+            return table.to_lines();
+        }
+        let mut lines = Vec::new();
+        let mut push = |formatted: &str| {
+            lines.extend(formatted.lines().map(str::to_string));
+        };
+        let widths = self.column_widths();
+        let width = self.rendered_width(&widths);
+        let mut buf = String::new();
+        for line in self.centered_lines(self.title.as_deref(), width) {
+            self.push_line(&mut buf, &line);
+        }
+        push(&buf);
 
-    #[test]
-    fn correct_default_padding() {
-        let table = Table::builder()
+        let grid = self.place_cells();
+        let content = self.row_span_content(&grid, &widths);
+        let rows = self.grid_rows(&grid, Some(&content));
+        self.write_rows(&widths, &grid, rows, |_, formatted| {
+            push(&formatted);
+            Ok(())
+        })
+        .expect("formatting rows doesn't fail");
+
+        buf.clear();
+        for line in self.centered_lines(self.caption.as_deref(), width) {
+            self.push_line(&mut buf, &line);
+        }
+        push(&buf);
+        lines
+    }
+
+    /// Renders the table into an existing `String`, replacing its contents.
+    ///
+    /// The allocation of `buf` is reused, which avoids allocating a new `String` when a table is redrawn often
+    pub fn render_into(&self, buf: &mut String) {
+        let mut print_buffer = std::mem::take(buf).into_bytes();
+        print_buffer.clear();
+        self.render_to(&mut print_buffer)
+            .expect("Writing to a Vec should never fail");
+        *buf =
+            String::from_utf8(print_buffer).expect("Rendered tables should always be valid UTF-8");
+    }
+
+    /// Renders the table directly to a writer.
+    ///
+    /// Each line is written as soon as it is formatted, which avoids building the entire table
+    /// in memory when writing large tables to something like stdout
+    pub fn render_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        if let Some(table) = self.visible_table() {
+            return table.render_to(writer);
+        }
+        let widths = self.column_widths();
+        let width = self.rendered_width(&widths);
+        for line in self.centered_lines(self.title.as_deref(), width) {
+            self.write_line(writer, &line)?;
+        }
+        self.render_with_widths(writer, &widths)?;
+        for line in self.centered_lines(self.caption.as_deref(), width) {
+            self.write_line(writer, &line)?;
+        }
+        Ok(())
+    }
+
+    /// Iterates over the rendered rows of the table along with their positions.
+    ///
+    /// Each item holds the lines of a row, including the separator above it, or the bottom border of the table.
+    /// The first row is `RowPosition::First`, and the bottom border is `RowPosition::Last`, or the last row if there
+    /// isn't a bottom border. Joining the items gives the rendered table without its title and caption
+    pub fn iter_rendered(&self) -> impl Iterator<Item = (RowPosition, String)> {
+        self.rendered_rows().into_iter()
+    }
+
+    /// The rendered rows of the table for `iter_rendered`
+    fn rendered_rows(&self) -> Vec<(RowPosition, String)> {
+        if let Some(table) = self.visible_table() {
+            return table.rendered_rows();
+        }
+        let max_widths = self.column_widths();
+        let grid = self.place_cells();
+        let content = self.row_span_content(&grid, &max_widths);
+        let rows = self.grid_rows(&grid, Some(&content));
+        let mut rendered = Vec::new();
+        self.write_rows(&max_widths, &grid, rows, |position, lines| {
+            rendered.push((position, lines));
+            Ok(())
+        })
+        .expect("formatting rows doesn't fail");
+        if !self.has_bottom_boarder && rendered.len() > 1 {
+            let last = rendered.len() - 1;
+            rendered[last].0 = RowPosition::Last;
+        }
+        rendered
+    }
+
+    /// Wraps text to the width of the table and centers each of its lines, for the title and caption
+    fn centered_lines(&self, text: Option<&str>, width: usize) -> Vec<String> {
+        let text = match text {
+            Some(text) => text,
+            None => return Vec::new(),
+        };
+        let cell = TableCell::builder(text)
+            .wrap_style(WrapStyle::Word)
+            .pad_content(false)
+            .build();
+        self.wrapped_content(&cell, width)
+            .into_iter()
+            .map(|line| {
+                let line = line.replace('\0', "");
+                let padding = width.saturating_sub(string_width(&line));
+                let left = padding - padding / 2;
+                format!("{}{}{}", " ".repeat(left), line, " ".repeat(padding - left))
+            })
+            .collect()
+    }
+
+    /// Renders the table in pages that each contain up to `rows_per_page` rows.
+    ///
+    /// Every page is a complete table starting with the header, if there is one. The column widths
+    /// are calculated using all of the rows so the columns of every page line up
+    pub fn render_pages(&self, rows_per_page: usize) -> Vec<String> {
+        if let Some(table) = self.visible_table() {
+            return table.render_pages(rows_per_page);
+        }
+        let widths = self.column_widths();
+        let header_len = if self.has_header {
+            min(1, self.rows.len())
+        } else {
+            0
+        };
+        let (header, rows) = self.rows.split_at(header_len);
+        if rows.is_empty() {
+            return vec![self.render()];
+        }
+
+        let template = Table {
+            rows: Vec::new(),
+            ..self.clone()
+        };
+        rows.chunks(max(rows_per_page, 1))
+            .map(|page_rows| {
+                let page = Table {
+                    rows: header.iter().chain(page_rows).cloned().collect(),
+                    ..template.clone()
+                };
+                let mut print_buffer = Vec::new();
+                page.render_with_widths(&mut print_buffer, &widths)
+                    .expect("Writing to a Vec should never fail");
+                String::from_utf8(print_buffer)
+                    .expect("Rendered tables should always be valid UTF-8")
+            })
+            .collect()
+    }
+
+    /// Splits the columns of the table into panels that each fit within `max_width` and renders each panel as its own table.
+    ///
+    /// The columns of every panel keep the widths they have when the whole table is rendered. Columns that are
+    /// wider than `max_width` on their own are placed in a panel by themselves, which will be wider than `max_width`.
+    /// Cells spanning columns in more than one panel are shown in each of them
+    pub fn render_column_pages(&self, max_width: usize) -> Vec<String> {
+        self.render_column_pages_with_key(max_width, None)
+    }
+
+    /// Splits the columns of the table into panels like `render_column_pages`, repeating the `key_column`
+    /// as the first column of each panel so the rows of each panel can be identified
+    pub fn render_column_pages_with_key(
+        &self,
+        max_width: usize,
+        key_column: Option<usize>,
+    ) -> Vec<String> {
+        if let Some(table) = self.visible_table() {
+            let key_column = key_column.and_then(|column| self.visible_column(column));
+            return table.render_column_pages_with_key(max_width, key_column);
+        }
+        let widths = self.column_widths();
+        let key_column = key_column.filter(|column| *column < widths.len());
+
+        // Each panel is made up of segments of adjacent columns
+        let mut panels: Vec<Vec<Vec<usize>>> = Vec::new();
+        let mut segments: Vec<Vec<usize>> = key_column.iter().map(|key| vec![*key]).collect();
+        for column in (0..widths.len()).filter(|column| Some(*column) != key_column) {
+            let mut columns: Vec<usize> = segments.iter().flatten().copied().collect();
+            columns.push(column);
+            let panel_widths: Vec<usize> = columns.iter().map(|column| widths[*column]).collect();
+            let has_other_columns = columns.len() > 1 + key_column.iter().count();
+            if has_other_columns && self.rendered_width(&panel_widths) > max_width {
+                panels.push(std::mem::take(&mut segments));
+                segments.extend(key_column.iter().map(|key| vec![*key]));
+            }
+            match segments.last_mut() {
+                Some(segment) if segment.last().map(|last| last + 1) == Some(column) => {
+                    segment.push(column)
+                }
+                _ => segments.push(vec![column]),
+            }
+        }
+        if segments.iter().flatten().count() > 0 {
+            panels.push(segments);
+        }
+
+        panels
+            .iter()
+            .map(|segments| {
+                let panel_widths: Vec<usize> = segments
+                    .iter()
+                    .flatten()
+                    .map(|column| widths[*column])
+                    .collect();
+                let mut print_buffer = Vec::new();
+                self.select_columns(segments)
+                    .render_with_widths(&mut print_buffer, &panel_widths)
+                    .expect("Writing to a Vec should never fail");
+                String::from_utf8(print_buffer)
+                    .expect("Rendered tables should always be valid UTF-8")
+            })
+            .collect()
+    }
+
+    /// Creates a copy of the table containing only some of its columns.
+    ///
+    /// The columns are given as segments in ascending order. Cells are placed in each segment containing
+    /// any of their columns, spanning only the columns of the segment they cover
+    fn select_columns(&self, segments: &[Vec<usize>]) -> Table {
+        let grid = self.place_cells();
+        let rows = grid
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, slots)| {
+                let mut row = Row::empty();
+                row.has_separator = self.rows[row_idx].has_separator;
+                row.alignment = self.rows[row_idx].alignment;
+                row.style = self.rows[row_idx].style.clone();
+                for segment in segments {
+                    let mut column = 0;
+                    for slot in slots {
+                        let (cell, col_span) = match slot {
+                            GridSlot::Cell(cell_idx, col_span) => {
+                                (Some(&self.rows[row_idx].cells[*cell_idx]), *col_span)
+                            }
+                            GridSlot::RowSpan(span_idx) => {
+                                let span = &grid.row_spans[*span_idx];
+                                let cell = &self.rows[span.row].cells[span.cell];
+                                // The cell is only placed in its first row
+                                (Some(cell).filter(|_| span.row == row_idx), span.col_span)
+                            }
+                            GridSlot::Empty => (None, 1),
+                        };
+                        let covered = segment
+                            .iter()
+                            .filter(|c| (column..column + col_span).contains(c))
+                            .count();
+                        column += col_span;
+                        if covered == 0 {
+                            continue;
+                        }
+                        match (slot, cell) {
+                            (_, Some(cell)) => row.add_cell(TableCell {
+                                col_span: covered,
+                                ..cell.clone()
+                            }),
+                            (GridSlot::Empty, None) => {
+                                row.add_cell(TableCell::builder("").pad_content(false).build())
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+                row
+            })
+            .collect();
+
+        // Settings for specific columns are moved to the new positions of the columns
+        let columns: Vec<usize> = segments.iter().flatten().copied().collect();
+        fn remap<T: Copy>(columns: &[usize], map: &HashMap<usize, T>) -> HashMap<usize, T> {
+            columns
+                .iter()
+                .enumerate()
+                .filter_map(|(i, column)| map.get(column).map(|value| (i, *value)))
+                .collect()
+        }
+        Table {
+            rows,
+            max_column_widths: remap(&columns, &self.max_column_widths),
+            fixed_column_widths: remap(&columns, &self.fixed_column_widths),
+            column_alignments: remap(&columns, &self.column_alignments),
+            hidden_columns: HashSet::new(),
+            padded_column: self
+                .padded_column
+                .and_then(|column| columns.iter().position(|c| *c == column)),
+            ..self.unfrozen()
+        }
+    }
+
+    /// A copy of the table without its hidden columns and skipped empty rows,
+    /// or `None` if nothing is left out
+    fn visible_table(&self) -> Option<Table> {
+        if self.rtl {
+            return Some(self.mirrored());
+        }
+        let header_len = if self.has_header { 1 } else { 0 };
+        // Section breaks have no content but are kept
+        let is_skipped = |row: &Row| !row.section_break && row.is_empty();
+        let skips_rows = self.skip_empty_rows && self.rows.iter().skip(header_len).any(is_skipped);
+        if self.hidden_columns.is_empty() && !skips_rows {
+            return None;
+        }
+
+        let mut table = if self.hidden_columns.is_empty() {
+            self.unfrozen()
+        } else {
+            let columns = (0..self.column_count())
+                .filter(|column| !self.hidden_columns.contains(column))
+                .collect();
+            self.select_columns(&[columns])
+        };
+        if skips_rows {
+            let mut index = 0;
+            table.rows.retain(|row| {
+                index += 1;
+                index <= header_len || !is_skipped(row)
+            });
+        }
+        Some(table)
+    }
+
+    /// Applies `visible_table` until nothing else is left out, or `None` if nothing is left out to begin with
+    fn fully_visible_table(&self) -> Option<Table> {
+        let mut table = self.visible_table()?;
+        while let Some(next) = table.visible_table() {
+            table = next;
+        }
+        Some(table)
+    }
+
+    /// The index `column` has in the table returned by `visible_table`, or `None` if it's hidden
+    fn visible_column(&self, column: usize) -> Option<usize> {
+        if self.rtl {
+            return self.column_count().checked_sub(column + 1);
+        }
+        if self.hidden_columns.contains(&column) {
+            return None;
+        }
+        Some(
+            column
+                - self
+                    .hidden_columns
+                    .iter()
+                    .filter(|hidden| **hidden < column)
+                    .count(),
+        )
+    }
+
+    /// A copy of the table with its columns in reverse order and its left and right alignments swapped, for `rtl`
+    fn mirrored(&self) -> Table {
+        fn flip(alignment: Alignment) -> Alignment {
+            match alignment {
+                Alignment::Left => Alignment::Right,
+                Alignment::Right => Alignment::Left,
+                alignment => alignment,
+            }
+        }
+        // Placeholders are filled in here so that they aren't added to the cells filling in short rows
+        let flip_cell = |cell: &TableCell| TableCell {
+            data: if cell.data.is_empty() {
+                self.none_placeholder.clone()
+            } else {
+                cell.data.clone()
+            },
+            alignment: flip(cell.alignment),
+            center_bias: match cell.center_bias {
+                CenterBias::Left => CenterBias::Right,
+                CenterBias::Right => CenterBias::Left,
+            },
+            ..cell.clone()
+        };
+
+        let column_count = self.column_count();
+        let grid = self.place_cells();
+        let rows = grid
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, slots)| {
+                let source = &self.rows[row_idx];
+                let mut cells = Vec::new();
+                let mut columns = 0;
+                for slot in slots {
+                    match slot {
+                        GridSlot::Cell(cell_idx, col_span) => {
+                            columns += col_span;
+                            cells.push(TableCell {
+                                col_span: *col_span,
+                                ..flip_cell(&source.cells[*cell_idx])
+                            });
+                        }
+                        // Cells spanning down from a previous row are placed again by the mirrored table
+                        GridSlot::RowSpan(span_idx) => {
+                            let span = &grid.row_spans[*span_idx];
+                            columns += span.col_span;
+                            if span.row == row_idx {
+                                cells.push(TableCell {
+                                    col_span: span.col_span,
+                                    ..flip_cell(&source.cells[span.cell])
+                                });
+                            }
+                        }
+                        GridSlot::Empty => {
+                            columns += 1;
+                            cells.push(TableCell::builder("").pad_content(false).build());
+                        }
+                    }
+                }
+                // Rows that don't reach the last column are filled in from the left
+                cells.extend((columns..column_count).map(|_| TableCell::new("")));
+                cells.reverse();
+                Row {
+                    cells,
+                    alignment: source.alignment.map(flip),
+                    ..source.clone()
+                }
+            })
+            .collect();
+
+        // Settings for columns past the last one are dropped
+        let mirror = |column: usize| column_count.checked_sub(column + 1);
+        fn remap<T: Copy>(
+            map: &HashMap<usize, T>,
+            mirror: impl Fn(usize) -> Option<usize>,
+        ) -> HashMap<usize, T> {
+            map.iter()
+                .filter_map(|(column, value)| Some((mirror(*column)?, *value)))
+                .collect()
+        }
+        Table {
+            rows,
+            max_column_widths: remap(&self.max_column_widths, mirror),
+            fixed_column_widths: remap(&self.fixed_column_widths, mirror),
+            // Columns without an alignment are left aligned, so they become right aligned
+            column_alignments: (0..column_count)
+                .map(|column| {
+                    let alignment = self.column_alignments.get(&column).copied();
+                    (
+                        column_count - 1 - column,
+                        flip(alignment.unwrap_or(Alignment::Left)),
+                    )
+                })
+                .collect(),
+            header_alignment: self.header_alignment.map(flip),
+            hidden_columns: self
+                .hidden_columns
+                .iter()
+                .filter_map(|c| mirror(*c))
+                .collect(),
+            padded_column: self
+                .padded_column
+                .or(column_count.checked_sub(1))
+                .and_then(mirror),
+            none_placeholder: String::new(),
+            rtl: false,
+            ..self.unfrozen()
+        }
+    }
+
+    /// Renders the table to a writer using already calculated column widths
+    fn render_with_widths<W: Write>(&self, writer: &mut W, max_widths: &[usize]) -> io::Result<()> {
+        let grid = self.place_cells();
+        let content = self.row_span_content(&grid, max_widths);
+        let rows = self.grid_rows(&grid, Some(&content));
+        self.write_rows(max_widths, &grid, rows, |_, lines| {
+            writer.write_all(lines.as_bytes())
+        })
+    }
+
+    /// Renders the rows of the table followed by rows from an iterator, without collecting the rows from the iterator.
+    ///
+    /// Each row is written as soon as it is taken from the iterator, so rows can be rendered as they arrive and
+    /// don't all need to be kept in memory. Since the rows can't be measured in advance, the column widths are
+    /// calculated from the rows already in the table, such as its header, and replaced by `fixed_column_widths`.
+    /// Content that doesn't fit in these widths is wrapped. Cells in the iterator spanning multiple rows are
+    /// treated as if they only span a single row
+    pub fn render_streaming<I, W>(&self, rows: I, writer: &mut W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+        W: Write,
+    {
+        let table = match self.fully_visible_table() {
+            Some(table) => table,
+            None => return self.write_streaming(rows, writer),
+        };
+        // Each row from the iterator has the columns and rows of the table left out of it as well
+        let mut template = Table {
+            rows: Vec::new(),
+            has_header: false,
+            ..self.unfrozen()
+        };
+        let column_count = self.column_count();
+        let rows = rows.into_iter().filter_map(move |mut row| {
+            // Short rows are filled in so they're mirrored against the last column of the table
+            if template.rtl {
+                let missing = column_count.saturating_sub(row.num_columns());
+                row.cells.extend((0..missing).map(|_| TableCell::new("")));
+            }
+            template.rows = vec![row];
+            match template.fully_visible_table() {
+                Some(mut visible) => visible.rows.pop(),
+                None => template.rows.pop(),
+            }
+        });
+        table.write_streaming(rows, writer)
+    }
+
+    /// Renders the table followed by the rows from an iterator for `render_streaming`, once the
+    /// columns and rows that are left out have been removed
+    fn write_streaming<I, W>(&self, rows: I, writer: &mut W) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+        W: Write,
+    {
+        let max_widths = self.column_widths();
+        let grid = self.place_cells();
+        let content = self.row_span_content(&grid, &max_widths);
+        let table_rows = self.grid_rows(&grid, Some(&content));
+        let rows = table_rows
+            .into_iter()
+            .chain(rows.into_iter().map(|mut row| {
+                let mut column = 0;
+                for cell in &mut row.cells {
+                    cell.row_span = 1;
+                    if cell.data.is_empty() {
+                        cell.data = self.none_placeholder.clone();
+                    }
+                    if cell.inherit_alignment {
+                        cell.set_own_alignment(
+                            row.alignment
+                                .or_else(|| self.column_alignments.get(&column).copied()),
+                        );
+                    }
+                    cell.ambiguous_wide |= self.ambiguous_wide;
+                    if self.strip_ansi {
+                        cell.data = strip_ansi(&cell.data);
+                    }
+                    if self.strip_ansi || self.no_color {
+                        cell.fg = None;
+                        cell.bg = None;
+                    }
+                    column += cell.col_span;
+                }
+                row
+            }));
+        self.write_rows(&max_widths, &grid, rows, |_, lines| {
+            writer.write_all(lines.as_bytes())
+        })
+    }
+
+    /// Formats each row along with the separator above it, then the bottom border of the table.
+    ///
+    /// The lines of each row and of the bottom border are passed to `emit` as soon as they are formatted,
+    /// along with the position of the row. Every line ends with the table's line ending
+    fn write_rows<I, F>(
+        &self,
+        max_widths: &[usize],
+        grid: &Grid,
+        rows: I,
+        mut emit: F,
+    ) -> io::Result<()>
+    where
+        I: IntoIterator<Item = Row>,
+        F: FnMut(RowPosition, String) -> io::Result<()>,
+    {
+        let inner_style = self.inner_style.as_ref().unwrap_or(&self.style);
+        let options = FormatOptions {
+            outer: &self.style,
+            inner: inner_style,
+            row_padding: self.row_padding,
+            has_left_border: self.has_left_border,
+            has_right_border: self.has_right_border,
+            separate_columns: self.separate_columns,
+        };
+        let bottom_border = |row: &Row, lines: &mut String| {
+            let separator =
+                row.gen_separator_with_options(max_widths, &options, RowPosition::Last, None);
+            let style = row.separator_style(&options, RowPosition::Last);
+            self.push_line(lines, &style.fill_horizontal(&separator));
+        };
+        // Separators drawn on their own in another style, which are merged with a separator above them in that style
+        let styled_separator = |outer: &TableStyle, inner: &TableStyle, above: &Row, row: &Row| {
+            let styled_options = FormatOptions {
+                outer,
+                inner,
+                ..options
+            };
+            let above = above.gen_separator_with_options(
+                max_widths,
+                &styled_options,
+                RowPosition::Mid,
+                None,
+            );
+            row.gen_separator_with_options(
+                max_widths,
+                &styled_options,
+                RowPosition::Mid,
+                Some(above),
+            )
+        };
+        // The outer and inner styles of the separator below the first row
+        let header_horizontal = self.header_horizontal.filter(|_| self.has_header);
+        let header_styles = match (&self.header_separator_style, header_horizontal) {
+            (Some(style), Some(horizontal)) => {
+                let style = style.with_horizontal(horizontal);
+                Some((style.clone(), style))
+            }
+            (Some(style), None) => Some((style.clone(), style.clone())),
+            (None, Some(horizontal)) => Some((
+                self.style.with_horizontal(horizontal),
+                inner_style.with_horizontal(horizontal),
+            )),
+            (None, None) => None,
+        };
+        let mut previous_separator = None;
+        let mut last_row: Option<Row> = None;
+        let mut section_start = true;
+        let mut rows = rows.into_iter().enumerate().peekable();
+        while let Some((i, row)) = rows.next() {
+            // Rows are only looked ahead of when needed, since `render_streaming` would wait for the next row
+            let is_last = self.footer_separator_style.is_some() && rows.peek().is_none();
+
+            // Each section is drawn like a table of its own, so the section above the break
+            // is closed with a bottom border and the next one starts with a top border
+            if row.section_break {
+                let mut lines = String::new();
+                if let (true, Some(last_row)) = (self.has_bottom_boarder, last_row.take()) {
+                    bottom_border(&last_row, &mut lines);
+                }
+                self.push_line(&mut lines, &" ".repeat(self.rendered_width(max_widths)));
+                emit(RowPosition::Mid, lines)?;
+                previous_separator = None;
+                section_start = true;
+                continue;
+            }
+
+            let row_pos = if section_start {
+                RowPosition::First
+            } else {
+                RowPosition::Mid
+            };
+
+            // Separators drawn in different styles can't be merged
+            let previous_style = last_row.as_ref().and_then(|row| row.style.as_ref());
+            if row.style.is_some() || previous_style.is_some() {
+                previous_separator = None;
+            }
+
+            let separator = row.gen_separator_with_options(
+                max_widths,
+                &options,
+                row_pos,
+                previous_separator.clone(),
+            );
+
+            // The top border is drawn with the outer style, but the separator below
+            // the first row needs to be merged with one drawn in the inner style
+            previous_separator = if section_start && self.inner_style.is_some() {
+                Some(row.gen_separator_with_options(max_widths, &options, RowPosition::Mid, None))
+            } else {
+                Some(separator.clone())
+            };
+
+            // The separators below the first row and above the last row are drawn on their own in their style,
+            // so the next separator is still merged with one in the table's style
+            let footer_style = self.footer_separator_style.as_ref().filter(|_| is_last);
+            let (separator, style) = match (i, &header_styles, footer_style, &last_row) {
+                (1, Some((outer, inner)), _, Some(above)) => (
+                    styled_separator(outer, inner, above, &row),
+                    row.style.as_ref().unwrap_or(inner),
+                ),
+                (_, _, Some(style), Some(above)) => (
+                    styled_separator(style, style, above, &row),
+                    row.style.as_ref().unwrap_or(style),
+                ),
+                _ => (separator, row.separator_style(&options, row_pos)),
+            };
+
+            let mut lines = String::new();
+            if self.has_separator_above(i, &row, section_start, is_last) {
+                let separator = self.clear_row_spans(&separator, max_widths, grid, i);
+                self.push_line(&mut lines, &style.fill_horizontal(&separator));
+            }
+
+            self.push_line(&mut lines, &row.format_with_options(max_widths, &options));
+            emit(row_pos, lines)?;
+            last_row = Some(row);
+            section_start = false;
+        }
+        if let (true, Some(last_row)) = (self.has_bottom_boarder, last_row) {
+            let mut lines = String::new();
+            bottom_border(&last_row, &mut lines);
+            emit(RowPosition::Last, lines)?;
+        }
+        Ok(())
+    }
+
+    /// Calculates the column widths once and reuses them for every render until the table changes.
+    ///
+    /// This saves measuring every cell each time a table that doesn't change is redrawn. Methods that
+    /// change the rows or column widths of the table discard the stored widths, but changes made
+    /// directly to its fields are not tracked. Call `freeze` again or `unfreeze` after making them
+    pub fn freeze(&mut self) {
+        self.frozen_widths = None;
+        self.frozen_widths = Some(self.column_widths());
+    }
+
+    /// Discards the column widths stored by `freeze` so they are calculated on every render again
+    pub fn unfreeze(&mut self) {
+        self.frozen_widths = None;
+    }
+
+    /// Whether the column widths have been stored by `freeze` and are still in use
+    pub fn is_frozen(&self) -> bool {
+        self.frozen_widths.is_some()
+    }
+
+    /// A copy of the table without the widths stored by `freeze`, for building tables that are
+    /// rendered differently from this one
+    fn unfrozen(&self) -> Table {
+        Table {
+            frozen_widths: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether a separator is drawn above the row at `index` when the table is rendered
+    ///
+    /// The first row of the table and the first row after a section break are below the top border.
+    /// The separators below the header and above the last row when there's a `footer_separator_style` are always drawn
+    fn has_separator_above(
+        &self,
+        index: usize,
+        row: &Row,
+        section_start: bool,
+        is_last: bool,
+    ) -> bool {
+        row.has_separator
+            && if section_start {
+                self.has_top_boarder
+            } else {
+                self.separate_rows
+                    || self.has_header && index == 1
+                    || is_last && self.footer_separator_style.is_some()
+            }
+    }
+
+    /// The width and number of lines of the rendered table, without rendering it.
+    ///
+    /// The width is measured in columns of the widest line, including the borders
+    pub fn rendered_size(&self) -> (usize, usize) {
+        let layout = self.layout();
+        (layout.total_width, layout.total_height)
+    }
+
+    /// Measures the columns and rows of the rendered table, without rendering it.
+    ///
+    /// Hidden columns and skipped empty rows are left out, like they are when the table is rendered
+    pub fn layout(&self) -> Layout {
+        if let Some(table) = self.visible_table() {
+            return table.layout();
+        }
+        let column_widths = self.column_widths();
+        let grid = self.place_cells();
+        let content = self.row_span_content(&grid, &column_widths);
+        let rows = self.grid_rows(&grid, Some(&content));
+        if rows.is_empty() {
+            return Layout {
+                column_widths,
+                row_heights: Vec::new(),
+                total_width: 0,
+                total_height: 0,
+            };
+        }
+
+        let total_width = self.rendered_width(&column_widths);
+        let mut total_height = self
+            .centered_lines(self.title.as_deref(), total_width)
+            .len()
+            + self
+                .centered_lines(self.caption.as_deref(), total_width)
+                .len();
+        let mut row_heights = Vec::with_capacity(rows.len());
+        let mut section_start = true;
+        for (i, row) in rows.iter().enumerate() {
+            let height = if row.section_break {
+                // The bottom border of the section above the break
+                if self.has_bottom_boarder && !section_start {
+                    total_height += 1;
+                }
+                section_start = true;
+                1
+            } else {
+                if self.has_separator_above(i, row, section_start, i + 1 == rows.len()) {
+                    total_height += 1;
+                }
+                section_start = false;
+                // A row always takes up at least one line, even when it has no content
+                max(row.height(&column_widths) + self.row_padding * 2, 1)
+            };
+            total_height += height;
+            row_heights.push(height);
+        }
+        // A table ending with a section break has no bottom border below it
+        if self.has_bottom_boarder && !section_start {
+            total_height += 1;
+        }
+
+        Layout {
+            column_widths,
+            row_heights,
+            total_width,
+            total_height,
+        }
+    }
+
+    /// The width of the table when it is rendered with the column widths, including its borders
+    fn rendered_width(&self, column_widths: &[usize]) -> usize {
+        let inner_style = self.inner_style.as_ref().unwrap_or(&self.style);
+        let mut width = column_widths.iter().sum::<usize>()
+            + inner_style.column_separator.width().unwrap_or_default()
+                * (column_widths.len().saturating_sub(1));
+        if self.has_left_border {
+            width += self.style.vertical.width().unwrap_or_default();
+        }
+        if self.has_right_border {
+            width += self.style.vertical.width().unwrap_or_default();
+        }
+        width
+    }
+
+    /// The width of each column when the table is rendered.
+    ///
+    /// This takes `max_column_width`, `max_column_widths` and cells spanning multiple columns into account
+    pub fn column_widths(&self) -> Vec<usize> {
+        if let Some(widths) = &self.frozen_widths {
+            return widths.clone();
+        }
+        let mut widths =
+            self.calculate_max_column_widths(&self.grid_rows(&self.place_cells(), None));
+        if let (Some(total), Some(last)) = (self.min_width, widths.len().checked_sub(1)) {
+            let width = self.rendered_width(&widths);
+            if width < total {
+                widths[self.padded_column.map_or(last, |column| min(column, last))] +=
+                    total - width;
+            }
+        }
+        widths
+    }
+
+    /// Renders the table so that its total width is `total`, sizing columns in proportion to
+    /// the width of their content.
+    ///
+    /// Columns are never made wider than their content or their width in `max_column_widths`,
+    /// and the space they don't use is given to the other columns
+    pub fn render_to_width(&self, total: usize) -> String {
+        let widths = self.proportional_column_widths(total);
+        let mut table = self.unfrozen();
+        table.max_column_widths = widths.into_iter().enumerate().collect();
+        table.render()
+    }
+
+    /// Distributes `total` minus the space used by borders across the columns in proportion
+    /// to the width of their content
+    fn proportional_column_widths(&self, total: usize) -> Vec<usize> {
+        let rows = self.grid_rows(&self.place_cells(), None);
+        let mut unbounded = self.unfrozen();
+        unbounded.max_column_width = usize::MAX;
+        unbounded.max_column_widths.clear();
+        let natural_widths = unbounded.calculate_max_column_widths(&rows);
+
+        let num_columns = natural_widths.len();
+        let mut min_widths = vec![0; num_columns];
+        for row in &rows {
+            for (i, (_, min_width)) in row.split_column_widths().into_iter().enumerate() {
+                min_widths[i] = max(min_widths[i], min_width);
+            }
+        }
+        let max_widths: Vec<usize> = (0..num_columns)
+            .map(|i| {
+                let max_width = self
+                    .max_column_widths
+                    .get(&i)
+                    .copied()
+                    .unwrap_or(usize::MAX);
+                max(min_widths[i], min(natural_widths[i], max_width))
+            })
+            .collect();
+
+        // Every column has a vertical character to its left plus one at the end of the table
+        let mut available = total.saturating_sub(num_columns + 1);
+        let mut widths: Vec<Option<usize>> = vec![None; num_columns];
+        loop {
+            let unsettled: Vec<usize> = (0..num_columns).filter(|i| widths[*i].is_none()).collect();
+            let natural_total: usize = unsettled.iter().map(|i| natural_widths[*i]).sum();
+            if unsettled.is_empty() || natural_total == 0 {
+                break;
+            }
+
+            // Columns whose share is out of bounds are settled and the rest share what's left
+            let shares: Vec<f64> = unsettled
+                .iter()
+                .map(|i| available as f64 * natural_widths[*i] as f64 / natural_total as f64)
+                .collect();
+            let mut settled = false;
+            for (&i, &share) in unsettled.iter().zip(&shares) {
+                let width = if share > max_widths[i] as f64 {
+                    max_widths[i]
+                } else if share < min_widths[i] as f64 {
+                    min_widths[i]
+                } else {
+                    continue;
+                };
+                widths[i] = Some(width);
+                available = available.saturating_sub(width);
+                settled = true;
+            }
+            if settled {
+                continue;
+            }
+
+            // Hand out the space lost to rounding to the columns that lost the most
+            let mut remainder =
+                available - shares.iter().map(|share| *share as usize).sum::<usize>();
+            let mut by_fraction: Vec<(usize, f64)> = unsettled.into_iter().zip(shares).collect();
+            by_fraction.sort_by(|a, b| b.1.fract().total_cmp(&a.1.fract()));
+            for (i, share) in by_fraction {
+                let width = share as usize;
+                let extra = min(remainder, min(1, max_widths[i] - width));
+                remainder -= extra;
+                widths[i] = Some(width + extra);
+            }
+        }
+
+        widths
+            .into_iter()
+            .zip(min_widths)
+            .map(|(width, min_width)| width.unwrap_or(min_width))
+            .collect()
+    }
+
+    /// Renders the table with `TableStyle::simple` and without colors, for snapshot tests.
+    ///
+    /// The output only depends on the content and layout of the table, such as its spans, alignments
+    /// and borders, so it doesn't change when the styles or colors of the table do. Lines always end with `\n`
+    pub fn render_canonical(&self) -> String {
+        let mut table = self.unfrozen();
+        table.style = TableStyle::simple();
+        table.inner_style = None;
+        table.header_separator_style = None;
+        table.footer_separator_style = None;
+        table.header_horizontal = None;
+        table.line_ending = LineEnding::Lf;
+        table.strip_ansi = true;
+        for row in table.rows.iter_mut() {
+            row.style = None;
+        }
+        table.render()
+    }
+
+    /// Renders the table as plain text for logs and other places that can't display formatting.
+    ///
+    /// Columns are separated by spaces and still lined up according to their alignment, but there
+    /// are no borders and ansi escape sequences are removed from the cell's content.
+    /// Truncated cells using the default ellipsis are marked with `...` instead
+    pub fn render_plain(&self) -> String {
+        let mut table = self.unfrozen();
+        table.style = TableStyle::empty();
+        table.inner_style = None;
+        table.separate_rows = false;
+        table.has_top_boarder = false;
+        table.has_bottom_boarder = false;
+        table.has_left_border = false;
+        table.has_right_border = false;
+        table.footer_separator_style = None;
+        // The header is kept as a regular row so no separator is drawn below it
+        if self.has_header {
+            if let Some(header) = table.rows.first_mut() {
+                for cell in header.cells.iter_mut() {
+                    cell.set_own_alignment(cell.own_alignment().or(self.header_alignment));
+                }
+            }
+            table.has_header = false;
+        }
+        for cell in table.rows.iter_mut().flat_map(|row| row.cells.iter_mut()) {
+            cell.data = strip_ansi(&cell.data);
+            cell.fg = None;
+            cell.bg = None;
+            if cell.ellipsis == "…" {
+                cell.ellipsis = "...".to_string();
+            }
+        }
+
+        table
+            .render()
+            .lines()
+            .map(|line| {
+                // Drop the null characters used by unpadded cells
+                format!("{}\n", line.replace('\0', "").trim_end())
+            })
+            .collect()
+    }
+
+    /// Renders the table as a GitHub flavored Markdown table.
+    ///
+    /// The first row is used as the header and the alignment of each column is taken from the
+    /// cells in the row below it. Markdown can't represent cells spanning multiple columns or rows,
+    /// so spanning cells are placed in their first column and row and the rest are left empty.
+    /// Ansi escape sequences are removed and new lines are replaced with `<br>`
+    pub fn render_markdown(&self) -> String {
+        if let Some(table) = self.visible_table() {
+            return table.render_markdown();
+        }
+        let rows = self.grid_rows(&self.place_cells(), None);
+        let num_columns = rows.iter().map(Row::num_columns).max().unwrap_or(0);
+        if num_columns == 0 {
+            return String::new();
+        }
+
+        // Lay the cells out by column so spanned columns are filled with empty cells
+        let table: Vec<Vec<(String, Option<Alignment>)>> = rows
+            .iter()
+            .map(|row| {
+                let mut columns = Vec::with_capacity(num_columns);
+                for cell in &row.cells {
+                    let data = strip_ansi(&cell.data)
+                        .replace('|', "\\|")
+                        .replace('\n', "<br>");
+                    columns.push((data, cell.own_alignment()));
+                    for _ in 1..cell.col_span {
+                        columns.push((String::new(), cell.own_alignment()));
+                    }
+                }
+                columns.resize(num_columns, (String::new(), None));
+                columns
+            })
+            .collect();
+
+        let mut widths = vec![3; num_columns];
+        for columns in &table {
+            for (i, (data, _)) in columns.iter().enumerate() {
+                widths[i] = max(widths[i], string_width(data));
+            }
+        }
+
+        let format_row = |cells: Vec<String>| {
+            let padded: Vec<String> = cells
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{}{}", cell, " ".repeat(width - string_width(cell))))
+                .collect();
+            format!("| {} |\n", padded.join(" | "))
+        };
+
+        let alignments = &table[min(1, table.len() - 1)];
+        let separators = alignments
+            .iter()
+            .zip(&widths)
+            .map(|((_, alignment), width)| match alignment {
+                None => "-".repeat(*width),
+                Some(Alignment::Left) | Some(Alignment::Justify) => {
+                    format!(":{}", "-".repeat(width - 1))
+                }
+                Some(Alignment::Right) | Some(Alignment::Decimal) => {
+                    format!("{}:", "-".repeat(width - 1))
+                }
+                Some(Alignment::Center) => format!(":{}:", "-".repeat(width - 2)),
+            })
+            .collect();
+
+        let mut rows = table
+            .into_iter()
+            .map(|columns| columns.into_iter().map(|(data, _)| data).collect());
+        let mut markdown = format_row(rows.next().unwrap());
+        markdown.push_str(&format_row(separators));
+        for row in rows {
+            markdown.push_str(&format_row(row));
+        }
+        markdown
+    }
+
+    /// Places every cell on a grid of columns.
+    ///
+    /// Cells spanning multiple rows occupy their columns in each of the rows below them,
+    /// so the cells of those rows are shifted to the right.
+    ///
+    /// The grid has as many columns as it takes for every cell to start in a column of its own.
+    /// The last cell of a row can't span past that, so a large `col_span` doesn't add columns to the table
+    fn place_cells(&self) -> Grid {
+        let mut grid = Grid {
+            rows: Vec::with_capacity(self.rows.len()),
+            row_spans: Vec::new(),
+            columns: 0,
+        };
+        // The column the last slot of each row starts in
+        let mut last_columns = Vec::with_capacity(self.rows.len());
+
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            let mut slots = Vec::new();
+            let mut cells = row.cells.iter().enumerate();
+            let mut column = 0;
+            let mut last_column = None;
+            // Spans starting in this row can't occupy any of its other columns
+            let active_spans = grid.row_spans.len();
+
+            loop {
+                let span = grid.row_spans[..active_spans]
+                    .iter()
+                    .position(|span| span.column == column && span.last_row >= row_idx);
+                if let Some(span_idx) = span {
+                    last_column = Some(column);
+                    slots.push(GridSlot::RowSpan(span_idx));
+                    column += grid.row_spans[span_idx].col_span;
+                } else if let Some((cell_idx, cell)) = cells.next() {
+                    last_column = Some(column);
+                    // A cell always takes up at least one column, even if its `col_span` field was set to 0
+                    let col_span = max(cell.col_span, 1);
+                    if cell.row_span > 1 {
+                        grid.row_spans.push(RowSpan {
+                            row: row_idx,
+                            cell: cell_idx,
+                            column,
+                            col_span,
+                            last_row: min(row_idx + cell.row_span, self.rows.len()) - 1,
+                        });
+                        slots.push(GridSlot::RowSpan(grid.row_spans.len() - 1));
+                    } else {
+                        slots.push(GridSlot::Cell(cell_idx, col_span));
+                    }
+                    column += col_span;
+                } else if grid.row_spans[..active_spans]
+                    .iter()
+                    .any(|span| span.column > column && span.last_row >= row_idx)
+                {
+                    // Fill the gap before a cell spanning down from a previous row
+                    last_column = Some(column);
+                    slots.push(GridSlot::Empty);
+                    column += 1;
+                } else {
+                    break;
+                }
+            }
+
+            grid.rows.push(slots);
+            last_columns.push(last_column);
+        }
+
+        grid.columns = last_columns
+            .iter()
+            .flatten()
+            .map(|column| column + 1)
+            .max()
+            .unwrap_or(0);
+        for (slots, last_column) in grid.rows.iter_mut().zip(last_columns) {
+            let (slot, column) = match (slots.last_mut(), last_column) {
+                (Some(slot), Some(column)) => (slot, column),
+                _ => continue,
+            };
+            match slot {
+                GridSlot::Cell(_, col_span) => *col_span = min(*col_span, grid.columns - column),
+                GridSlot::RowSpan(span_idx) => {
+                    let span = &mut grid.row_spans[*span_idx];
+                    span.col_span = min(span.col_span, grid.columns - column);
+                }
+                GridSlot::Empty => {}
+            }
+        }
+
+        grid
+    }
+
+    /// Builds the rows that are actually rendered from the grid.
+    ///
+    /// When `content` is provided each row a cell spans will contain its share of the cell's lines.
+    /// Otherwise the cell is only placed in its first row, which is all that's needed to calculate column widths.
+    ///
+    /// Cells without an alignment are given the default alignment of the header or the column they start in
+    fn grid_rows(&self, grid: &Grid, content: Option<&[Vec<String>]>) -> Vec<Row> {
+        let mut rows: Vec<Row> = grid
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, slots)| {
+                let mut row = Row::empty();
+                row.has_separator = self.rows[row_idx].has_separator;
+                row.alignment = self.rows[row_idx].alignment;
+                row.style = self.rows[row_idx].style.clone();
+                row.section_break = self.rows[row_idx].section_break;
+                let mut column = 0;
+                for slot in slots {
+                    let mut cell = match slot {
+                        GridSlot::Cell(cell_idx, col_span) => {
+                            let mut cell = self.rows[row_idx].cells[*cell_idx].clone();
+                            cell.col_span = *col_span;
+                            if cell.data.is_empty() {
+                                cell.data = self.none_placeholder.clone();
+                            }
+                            cell
+                        }
+                        GridSlot::RowSpan(span_idx) => {
+                            let span = &grid.row_spans[*span_idx];
+                            let original = &self.rows[span.row].cells[span.cell];
+                            match content {
+                                Some(content) => {
+                                    let mut cell =
+                                        TableCell::builder(&content[*span_idx][row_idx - span.row])
+                                            .col_span(span.col_span)
+                                            .verbatim(true)
+                                            .build();
+                                    cell.alignment = original.alignment;
+                                    cell.inherit_alignment = original.inherit_alignment;
+                                    cell.center_bias = original.center_bias;
+                                    cell.fg = original.fg;
+                                    cell.bg = original.bg;
+                                    cell
+                                }
+                                None if span.row == row_idx => TableCell {
+                                    col_span: span.col_span,
+                                    ..original.clone()
+                                },
+                                None => TableCell::builder("")
+                                    .col_span(span.col_span)
+                                    .pad_content(false)
+                                    .build(),
+                            }
+                        }
+                        GridSlot::Empty => TableCell::builder("").pad_content(false).build(),
+                    };
+                    if cell.inherit_alignment {
+                        cell.set_own_alignment(row.alignment);
+                    }
+                    if cell.inherit_alignment && self.has_header && row_idx == 0 {
+                        cell.set_own_alignment(self.header_alignment);
+                    }
+                    if cell.inherit_alignment {
+                        cell.set_own_alignment(self.column_alignments.get(&column).copied());
+                    }
+                    cell.ambiguous_wide |= self.ambiguous_wide;
+                    if self.strip_ansi {
+                        cell.data = strip_ansi(&cell.data);
+                    }
+                    if self.strip_ansi || self.no_color {
+                        cell.fg = None;
+                        cell.bg = None;
+                    }
+                    match self.control_chars {
+                        ControlChars::Keep => {}
+                        ControlChars::Replace => {
+                            cell.data = replace_control_chars(&cell.data, Some('·'))
+                        }
+                        ControlChars::Remove => cell.data = replace_control_chars(&cell.data, None),
+                    }
+                    column += cell.col_span;
+                    row.add_cell(cell);
+                }
+                row
+            })
+            .collect();
+        self.align_decimals(&mut rows);
+        rows
+    }
+
+    /// Lines up the decimal points of the cells in each column that are aligned with `Alignment::Decimal`.
+    ///
+    /// Spaces are added after the fractional part of each line so that every line in a column has the same
+    /// number of characters after its decimal point, then the cells are right aligned. Lines without a decimal
+    /// point are aligned as if they ended with one. The header and cells spanning multiple columns are only right aligned
+    fn align_decimals(&self, rows: &mut [Row]) {
+        let header_len = if self.has_header { 1 } else { 0 };
+        let fraction_width = |line: &str| {
+            line.find('.')
+                .map_or(0, |index| string_width(&line[index..]))
+        };
+        let is_aligned = |cell: &TableCell| {
+            cell.alignment == Alignment::Decimal && cell.col_span == 1 && !cell.verbatim
+        };
+
+        let mut fraction_widths: HashMap<usize, usize> = HashMap::new();
+        for row in rows.iter().skip(header_len) {
+            let mut column = 0;
+            for cell in &row.cells {
+                if is_aligned(cell) {
+                    let width = cell.data.split('\n').map(fraction_width).max().unwrap_or(0);
+                    let max_width = fraction_widths.entry(column).or_insert(0);
+                    *max_width = max(*max_width, width);
+                }
+                column += cell.col_span;
+            }
+        }
+
+        for row in rows.iter_mut().skip(header_len) {
+            let mut column = 0;
+            for cell in row.cells.iter_mut() {
+                if is_aligned(cell) {
+                    let width = fraction_widths[&column];
+                    cell.data = cell
+                        .data
+                        .split('\n')
+                        .map(|line| format!("{}{}", line, " ".repeat(width - fraction_width(line))))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                }
+                column += cell.col_span;
+            }
+        }
+
+        for cell in rows.iter_mut().flat_map(|row| row.cells.iter_mut()) {
+            if cell.alignment == Alignment::Decimal {
+                cell.alignment = Alignment::Right;
+            }
+        }
+    }
+
+    /// Splits the content of each cell spanning multiple rows into the lines that belong in each row.
+    ///
+    /// Rows are made taller when the spanning cell needs more lines than the rows provide
+    fn row_span_content(&self, grid: &Grid, column_widths: &[usize]) -> Vec<Vec<String>> {
+        if grid.row_spans.is_empty() {
+            return Vec::new();
+        }
+
+        let span_width = |column: usize, col_span: usize| {
+            column_widths[column..column + col_span]
+                .iter()
+                .sum::<usize>()
+                + col_span
+                - 1
+        };
+
+        // The height of each row without the cells that span multiple rows
+        let mut heights: Vec<usize> = grid
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(row_idx, slots)| {
+                let mut column = 0;
+                let mut height = 1;
+                for slot in slots {
+                    match slot {
+                        GridSlot::Cell(cell_idx, col_span) => {
+                            let cell = &self.rows[row_idx].cells[*cell_idx];
+                            height = max(
+                                height,
+                                self.wrapped_content(cell, span_width(column, *col_span))
+                                    .len(),
+                            );
+                            column += col_span;
+                        }
+                        GridSlot::RowSpan(span_idx) => column += grid.row_spans[*span_idx].col_span,
+                        GridSlot::Empty => column += 1,
+                    }
+                }
+                height
+            })
+            .collect();
+
+        let mut wrapped_spans = Vec::with_capacity(grid.row_spans.len());
+        for span in &grid.row_spans {
+            let cell = &self.rows[span.row].cells[span.cell];
+            let wrapped = self.wrapped_content(cell, span_width(span.column, span.col_span));
+            let available: usize = heights[span.row..=span.last_row].iter().sum();
+            if wrapped.len() > available {
+                heights[span.last_row] += wrapped.len() - available;
+            }
+            wrapped_spans.push(wrapped);
+        }
+
+        grid.row_spans
+            .iter()
+            .zip(wrapped_spans)
+            .map(|(span, wrapped)| {
+                let cell = &self.rows[span.row].cells[span.cell];
+                let total: usize = heights[span.row..=span.last_row].iter().sum();
+                let top_offset = match cell.vertical_alignment {
+                    VerticalAlignment::Top => 0,
+                    VerticalAlignment::Middle => (total - wrapped.len()) / 2,
+                    VerticalAlignment::Bottom => total - wrapped.len(),
+                };
+                let mut lines = vec![String::new(); top_offset];
+                lines.extend(wrapped);
+                lines.resize(total, String::new());
+
+                let mut lines = lines.into_iter();
+                heights[span.row..=span.last_row]
+                    .iter()
+                    .map(|height| lines.by_ref().take(*height).collect::<Vec<_>>().join("\n"))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Wraps the content of a cell using the settings of the table
+    fn wrapped_content(&self, cell: &TableCell, width: usize) -> Vec<String> {
+        if self.ambiguous_wide && !cell.ambiguous_wide {
+            TableCell {
+                ambiguous_wide: true,
+                ..cell.clone()
+            }
+            .wrapped_content(width)
+        } else {
+            cell.wrapped_content(width)
+        }
+    }
+
+    /// Removes the horizontal line from a separator wherever a cell from a previous row spans across it
+    fn clear_row_spans(
+        &self,
+        separator: &str,
+        column_widths: &[usize],
+        grid: &Grid,
+        row_idx: usize,
+    ) -> String {
+        let crossings: Vec<&RowSpan> = grid
+            .row_spans
+            .iter()
+            .filter(|span| span.row < row_idx && span.last_row >= row_idx)
+            .collect();
+        if crossings.is_empty() {
+            return separator.to_string();
+        }
+
+        // The index of the character drawn at the left edge of each column
+        let mut boundaries = vec![0];
+        for width in column_widths {
+            boundaries.push(boundaries.last().unwrap() + width + 1);
+        }
+
+        let crossed = |column: usize| {
+            crossings
+                .iter()
+                .any(|span| column >= span.column && column < span.column + span.col_span)
+        };
+
+        let mut chars: Vec<char> = separator.chars().collect();
+        // Missing borders are added back while clearing so the boundaries line up with the characters
+        if !self.has_left_border {
+            chars.insert(0, ' ');
+        }
+        if !self.has_right_border {
+            chars.push(' ');
+        }
+        for span in &crossings {
+            let start = boundaries[span.column];
+            let end = boundaries[span.column + span.col_span];
+            for c in chars.iter_mut().take(end).skip(start + 1) {
+                *c = ' ';
+            }
+            for column in [span.column, span.column + span.col_span] {
+                // The horizontal line continues on the side of the boundary that isn't spanned
+                let left_open = column > 0 && !crossed(column - 1);
+                let right_open = column < column_widths.len() && !crossed(column);
+                let style = if column == 0 || column == column_widths.len() {
+                    &self.style
+                } else {
+                    self.inner_style.as_ref().unwrap_or(&self.style)
+                };
+                chars[boundaries[column]] = if left_open {
+                    style.outer_right_vertical
+                } else if right_open {
+                    style.outer_left_vertical
+                } else if column == 0 || column == column_widths.len() {
+                    style.vertical
+                } else {
+                    style.column_separator
+                };
+            }
+        }
+
+        if !self.has_left_border {
+            chars.remove(0);
+        }
+        if !self.has_right_border {
+            chars.pop();
+        }
+
+        chars.into_iter().collect()
+    }
+
+    /// Calculates the maximum width for each column.
+    /// If a cell has a column span greater than 1, then the width
+    /// of it's contents are divided by the column span, otherwise the cell
+    /// would use more space than it needed.
+    ///
+    /// The rows come from `grid_rows`, so their column spans are already cut short to the columns of the table.
+    fn calculate_max_column_widths(&self, rows: &[Row]) -> Vec<usize> {
+        let mut num_columns = 0;
+
+        for row in rows {
+            num_columns = max(row.num_columns(), num_columns);
+        }
+        let mut max_widths: Vec<usize> = vec![0; num_columns];
+        let mut min_widths: Vec<usize> = vec![0; num_columns];
+        for row in rows {
+            let column_widths = row.split_column_widths();
+            for i in 0..column_widths.len() {
+                min_widths[i] = max(min_widths[i], column_widths[i].1);
+                let mut max_width = *self
+                    .max_column_widths
+                    .get(&i)
+                    .unwrap_or(&self.max_column_width);
+                max_width = max(min_widths[i], max_width);
+                max_widths[i] = min(max_width, max(max_widths[i], column_widths[i].0 as usize));
+            }
+        }
+
+        // The widest override in each column wins
+        let mut overrides: HashMap<usize, usize> = HashMap::new();
+        for row in rows {
+            let mut column = 0;
+            for cell in &row.cells {
+                if let (1, Some(width)) = (cell.col_span, cell.width_override) {
+                    let width = max(width, overrides.get(&column).copied().unwrap_or(0));
+                    overrides.insert(column, width);
+                }
+                column += cell.col_span;
+            }
+        }
+        for (column, width) in overrides {
+            max_widths[column] = width;
+        }
+
+        let fixed_columns = self
+            .fixed_column_widths
+            .keys()
+            .map(|column| column + 1)
+            .max()
+            .unwrap_or(0);
+        if max_widths.len() < fixed_columns {
+            max_widths.resize(fixed_columns, 0);
+        }
+        for (&column, &width) in &self.fixed_column_widths {
+            max_widths[column] = width;
+        }
+
+        max_widths
+    }
+
+    /// Helper method for writing a line to a writer
+    fn write_line<W: Write>(&self, writer: &mut W, line: &str) -> io::Result<()> {
+        let mut buf = String::new();
+        self.push_line(&mut buf, line);
+        writer.write_all(buf.as_bytes())
+    }
+
+    /// Adds a line to a string followed by the table's line ending
+    fn push_line(&self, buf: &mut String, line: &str) {
+        // Unpadded cells and the blank style use null characters, which are as invisible as spaces
+        let blank_border = !self.has_right_border || matches!(self.style.vertical, ' ' | '\0');
+        let trimmed;
+        let line = if self.trim_trailing_whitespace && blank_border {
+            trimmed = line
+                .split('\n')
+                .map(|line| line.trim_end_matches(&[' ', '\0'][..]))
+                .collect::<Vec<_>>()
+                .join("\n");
+            &trimmed
+        } else {
+            line
+        };
+        match self.line_ending {
+            LineEnding::Lf => {
+                buf.push_str(line);
+                buf.push('\n');
+            }
+            // Rows taller than one line are formatted with line feeds between their lines
+            LineEnding::CrLf => {
+                buf.push_str(&line.replace('\n', "\r\n"));
+                buf.push_str("\r\n");
+            }
+        }
+    }
+}
+
+/// Splits a row of a Markdown table into the content of its cells
+fn split_markdown_row(line: &str) -> Vec<String> {
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = match line.strip_suffix('|') {
+        Some(stripped) if !stripped.ends_with('\\') => stripped,
+        _ => line,
+    };
+
+    let mut cells = Vec::new();
+    let mut cell = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('|') => cell.push('|'),
+                Some(next) => {
+                    cell.push('\\');
+                    cell.push(next);
+                }
+                None => cell.push('\\'),
+            },
+            '|' => cells.push(std::mem::take(&mut cell)),
+            c => cell.push(c),
+        }
+    }
+    cells.push(cell);
+
+    cells
+        .iter()
+        .map(|cell| cell.trim().replace("<br>", "\n"))
+        .collect()
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Table::new()
+    }
+}
+
+impl std::iter::FromIterator<Row> for Table {
+    fn from_iter<I: IntoIterator<Item = Row>>(rows: I) -> Self {
+        let mut table = Table::new();
+        table.extend(rows);
+        table
+    }
+}
+
+/// Creates a table with a row for each inner array.
+///
+/// ```
+/// use term_table::Table;
+///
+/// let table = Table::from([[1, 2, 3], [4, 5, 6]]);
+/// assert_eq!(
+///     table.render(),
+///     "╔═══╦═══╦═══╗\n\
+///      ║ 1 ║ 2 ║ 3 ║\n\
+///      ╠═══╬═══╬═══╣\n\
+///      ║ 4 ║ 5 ║ 6 ║\n\
+///      ╚═══╩═══╩═══╝\n"
+/// );
+/// ```
+impl<T: ToString, const N: usize, const M: usize> From<[[T; N]; M]> for Table {
+    fn from(rows: [[T; N]; M]) -> Self {
+        rows.iter()
+            .map(|row| Row::new(row.iter().map(|value| TableCell::new(value.to_string()))))
+            .collect()
+    }
+}
+
+/// Creates a table with a row for each inner `Vec`
+impl<T: ToString> From<Vec<Vec<T>>> for Table {
+    fn from(rows: Vec<Vec<T>>) -> Self {
+        rows.iter()
+            .map(|row| Row::new(row.iter().map(|value| TableCell::new(value.to_string()))))
+            .collect()
+    }
+}
+
+impl Extend<Row> for Table {
+    fn extend<I: IntoIterator<Item = Row>>(&mut self, rows: I) {
+        self.frozen_widths = None;
+        self.rows.extend(rows);
+    }
+}
+
+impl std::fmt::Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render())
+    }
+}
+
+/// Used to create non-mutable tables
+#[derive(Clone, Debug)]
+pub struct TableBuilder {
+    rows: Vec<Row>,
+    style: TableStyle,
+    max_column_width: usize,
+    max_column_widths: HashMap<usize, usize>,
+    column_alignments: HashMap<usize, Alignment>,
+    separate_rows: bool,
+    has_top_boarder: bool,
+    has_bottom_boarder: bool,
+    header: Option<Row>,
+    header_horizontal: Option<char>,
+    header_alignment: Option<Alignment>,
+    inner_style: Option<TableStyle>,
+    row_padding: usize,
+    has_left_border: bool,
+    has_right_border: bool,
+    none_placeholder: String,
+    fixed_column_widths: HashMap<usize, usize>,
+    line_ending: LineEnding,
+    ambiguous_wide: bool,
+    hidden_columns: HashSet<usize>,
+    strip_ansi: bool,
+    skip_empty_rows: bool,
+    title: Option<String>,
+    caption: Option<String>,
+    header_separator_style: Option<TableStyle>,
+    separate_columns: bool,
+    control_chars: ControlChars,
+    min_width: Option<usize>,
+    padded_column: Option<usize>,
+    rtl: bool,
+    trim_trailing_whitespace: bool,
+    footer_separator_style: Option<TableStyle>,
+    no_color: bool,
+}
+
+impl TableBuilder {
+    pub fn new() -> TableBuilder {
+        TableBuilder {
+            rows: Vec::new(),
+            style: TableStyle::extended(),
+            max_column_width: usize::MAX,
+            max_column_widths: HashMap::new(),
+            column_alignments: HashMap::new(),
+            separate_rows: true,
+            has_top_boarder: true,
+            has_bottom_boarder: true,
+            header: None,
+            header_horizontal: None,
+            header_alignment: None,
+            inner_style: None,
+            row_padding: 0,
+            has_left_border: true,
+            has_right_border: true,
+            none_placeholder: String::new(),
+            fixed_column_widths: HashMap::new(),
+            line_ending: LineEnding::Lf,
+            ambiguous_wide: false,
+            hidden_columns: HashSet::new(),
+            strip_ansi: false,
+            skip_empty_rows: false,
+            title: None,
+            caption: None,
+            header_separator_style: None,
+            separate_columns: true,
+            control_chars: ControlChars::Keep,
+            min_width: None,
+            padded_column: None,
+            rtl: false,
+            trim_trailing_whitespace: false,
+            footer_separator_style: None,
+            no_color: false,
+        }
+    }
+
+    pub fn rows(&mut self, rows: Vec<Row>) -> &mut Self {
+        self.rows = rows;
+        self
+    }
+
+    pub fn style(&mut self, style: TableStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// The maximum width of all columns. Overridden by values in column_widths. Defaults to `std::usize::max`
+    pub fn max_column_width(&mut self, max_column_width: usize) -> &mut Self {
+        self.max_column_width = max_column_width;
+        self
+    }
+
+    /// The maximum widths of specific columns. Override max_column
+    pub fn max_column_widths(&mut self, max_column_widths: HashMap<usize, usize>) -> &mut Self {
+        self.max_column_widths = max_column_widths;
+        self
+    }
+
+    /// The default alignment of a column. Used by cells that don't have an alignment of their own
+    pub fn column_alignment(&mut self, column_index: usize, alignment: Alignment) -> &mut Self {
+        self.column_alignments.insert(column_index, alignment);
+        self
+    }
+
+    /// The characters written at the end of each line
+    pub fn line_ending(&mut self, line_ending: LineEnding) -> &mut Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Count East Asian characters of ambiguous width as two columns wide instead of one
+    pub fn ambiguous_wide(&mut self, ambiguous_wide: bool) -> &mut Self {
+        self.ambiguous_wide = ambiguous_wide;
+        self
+    }
+
+    /// Leaves a column out when the table is rendered
+    pub fn hide_column(&mut self, column_index: usize) -> &mut Self {
+        self.hidden_columns.insert(column_index);
+        self
+    }
+
+    /// Whether ansi escape sequences are removed from the content of the cells when rendering
+    pub fn strip_ansi(&mut self, strip_ansi: bool) -> &mut Self {
+        self.strip_ansi = strip_ansi;
+        self
+    }
+
+    /// Whether the colors given to cells are left out when rendering
+    pub fn no_color(&mut self, no_color: bool) -> &mut Self {
+        self.no_color = no_color;
+        self
+    }
+
+    /// Leaves out the colors given to cells if the `NO_COLOR` environment variable is set to a non-empty value.
+    ///
+    /// The variable is only read when this is called, so the table renders the same way afterwards
+    #[cfg(feature = "terminal")]
+    pub fn respect_no_color(&mut self) -> &mut Self {
+        self.no_color = matches!(std::env::var_os("NO_COLOR"), Some(value) if !value.is_empty());
+        self
+    }
+
+    /// A line centered above the table
+    pub fn title<T: ToString>(&mut self, title: T) -> &mut Self {
+        self.title = Some(title.to_string());
+        self
+    }
+
+    /// A line centered below the table
+    pub fn caption<T: ToString>(&mut self, caption: T) -> &mut Self {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    /// The style of the separator between the first and second rows
+    pub fn header_separator_style(&mut self, header_separator_style: TableStyle) -> &mut Self {
+        self.header_separator_style = Some(header_separator_style);
+        self
+    }
+
+    /// The style of the separator above the last row
+    pub fn footer_separator_style(&mut self, footer_separator_style: TableStyle) -> &mut Self {
+        self.footer_separator_style = Some(footer_separator_style);
+        self
+    }
+
+    /// How control characters other than new lines and tabs in the content of the cells are rendered
+    pub fn control_chars(&mut self, control_chars: ControlChars) -> &mut Self {
+        self.control_chars = control_chars;
+        self
+    }
+
+    /// Widens a column so that the rendered table is at least `min_width` wide
+    pub fn min_width(&mut self, min_width: usize) -> &mut Self {
+        self.min_width = Some(min_width);
+        self
+    }
+
+    /// The column that is widened to reach `min_width`, instead of the last column
+    pub fn padded_column(&mut self, padded_column: usize) -> &mut Self {
+        self.padded_column = Some(padded_column);
+        self
+    }
+
+    /// Whether the columns are laid out from right to left
+    pub fn rtl(&mut self, rtl: bool) -> &mut Self {
+        self.rtl = rtl;
+        self
+    }
+
+    /// Whether spaces at the end of each line are removed when the right border is blank or isn't drawn
+    pub fn trim_trailing_whitespace(&mut self, trim_trailing_whitespace: bool) -> &mut Self {
+        self.trim_trailing_whitespace = trim_trailing_whitespace;
+        self
+    }
+
+    /// Whether rows where every cell is empty are left out when rendering
+    pub fn skip_empty_rows(&mut self, skip_empty_rows: bool) -> &mut Self {
+        self.skip_empty_rows = skip_empty_rows;
+        self
+    }
+
+    /// The exact width of a column, regardless of its content
+    pub fn fixed_column_width(&mut self, column_index: usize, width: usize) -> &mut Self {
+        self.fixed_column_widths.insert(column_index, width);
+        self
+    }
+
+    /// A row that is always rendered first and visually separated from the rest of the table
+    pub fn header(&mut self, header: Row) -> &mut Self {
+        self.header = Some(header);
+        self
+    }
+
+    /// The horizontal character used for the separator below the header. Defaults to the style's `horizontal`.
+    /// With `━` or `═`, thin junctions such as `┼` are swapped for ones that join the heavier line
+    pub fn header_horizontal(&mut self, header_horizontal: char) -> &mut Self {
+        self.header_horizontal = Some(header_horizontal);
+        self
+    }
+
+    /// The default alignment of header cells that don't have an alignment of their own
+    pub fn header_alignment(&mut self, header_alignment: Alignment) -> &mut Self {
+        self.header_alignment = Some(header_alignment);
+        self
+    }
+
+    /// Whether or not to vertically separate rows in the table
+    pub fn separate_rows(&mut self, separate_rows: bool) -> &mut Self {
+        self.separate_rows = separate_rows;
+        self
+    }
+
+    /// Whether the table should have a top boarder.
+    /// Setting `has_separator` to false on the first row will have the same effect as setting this to false
+    pub fn has_top_boarder(&mut self, has_top_boarder: bool) -> &mut Self {
+        self.has_top_boarder = has_top_boarder;
+        self
+    }
+
+    /// Whether the table should have a bottom boarder
+    pub fn has_bottom_boarder(&mut self, has_bottom_boarder: bool) -> &mut Self {
+        self.has_bottom_boarder = has_bottom_boarder;
+        self
+    }
+
+    /// Whether the table should have a left border
+    pub fn has_left_border(&mut self, has_left_border: bool) -> &mut Self {
+        self.has_left_border = has_left_border;
+        self
+    }
+
+    /// Whether the table should have a right border
+    pub fn has_right_border(&mut self, has_right_border: bool) -> &mut Self {
+        self.has_right_border = has_right_border;
+        self
+    }
+
+    /// Whether columns are separated by vertical lines. When false the columns are separated by spaces
+    pub fn separate_columns(&mut self, separate_columns: bool) -> &mut Self {
+        self.separate_columns = separate_columns;
+        self
+    }
+
+    /// Sets which borders and separators are drawn. The separator below the header is always drawn
+    pub fn borders(&mut self, borders: Borders) -> &mut Self {
+        self.has_top_boarder(borders.contains(Borders::TOP))
+            .has_bottom_boarder(borders.contains(Borders::BOTTOM))
+            .has_left_border(borders.contains(Borders::LEFT))
+            .has_right_border(borders.contains(Borders::RIGHT))
+            .separate_rows(borders.contains(Borders::INNER_HORIZONTAL))
+            .separate_columns(borders.contains(Borders::INNER_VERTICAL))
+    }
+
+    /// Set the text drawn in place of cells that have no content
+    pub fn none_placeholder<T: ToString>(&mut self, none_placeholder: T) -> &mut Self {
+        self.none_placeholder = none_placeholder.to_string();
+        self
+    }
+
+    /// Set the number of blank lines above and below the content of each row
+    pub fn row_padding(&mut self, row_padding: usize) -> &mut Self {
+        self.row_padding = row_padding;
+        self
+    }
+
+    /// Set the style used for the separators and vertical lines inside the table
+    pub fn inner_style(&mut self, inner_style: TableStyle) -> &mut Self {
+        self.inner_style = Some(inner_style);
+        self
+    }
+
+    /// Build a Table using the current configuration
+    pub fn build(&self) -> Table {
+        Table {
+            rows: self.header.iter().chain(&self.rows).cloned().collect(),
+            style: self.style.clone(),
+            max_column_width: self.max_column_width,
+            max_column_widths: self.max_column_widths.clone(),
+            column_alignments: self.column_alignments.clone(),
+            separate_rows: self.separate_rows,
+            has_top_boarder: self.has_top_boarder,
+            has_bottom_boarder: self.has_bottom_boarder,
+            has_header: self.header.is_some(),
+            header_horizontal: self.header_horizontal,
+            header_alignment: self.header_alignment,
+            inner_style: self.inner_style.clone(),
+            row_padding: self.row_padding,
+            has_left_border: self.has_left_border,
+            has_right_border: self.has_right_border,
+            frozen_widths: None,
+            none_placeholder: self.none_placeholder.clone(),
+            fixed_column_widths: self.fixed_column_widths.clone(),
+            line_ending: self.line_ending,
+            ambiguous_wide: self.ambiguous_wide,
+            hidden_columns: self.hidden_columns.clone(),
+            strip_ansi: self.strip_ansi,
+            skip_empty_rows: self.skip_empty_rows,
+            title: self.title.clone(),
+            caption: self.caption.clone(),
+            header_separator_style: self.header_separator_style.clone(),
+            separate_columns: self.separate_columns,
+            control_chars: self.control_chars,
+            min_width: self.min_width,
+            padded_column: self.padded_column,
+            rtl: self.rtl,
+            trim_trailing_whitespace: self.trim_trailing_whitespace,
+            footer_separator_style: self.footer_separator_style.clone(),
+            no_color: self.no_color,
+        }
+    }
+
+    /// Build a Table using the current configuration, moving the rows into it instead of cloning them
+    pub fn build_owned(self) -> Table {
+        let mut rows = self.rows;
+        let has_header = self.header.is_some();
+        if let Some(header) = self.header {
+            rows.insert(0, header);
+        }
+        Table {
+            rows,
+            style: self.style,
+            max_column_width: self.max_column_width,
+            max_column_widths: self.max_column_widths,
+            column_alignments: self.column_alignments,
+            separate_rows: self.separate_rows,
+            has_top_boarder: self.has_top_boarder,
+            has_bottom_boarder: self.has_bottom_boarder,
+            has_header,
+            header_horizontal: self.header_horizontal,
+            header_alignment: self.header_alignment,
+            inner_style: self.inner_style,
+            row_padding: self.row_padding,
+            has_left_border: self.has_left_border,
+            has_right_border: self.has_right_border,
+            frozen_widths: None,
+            none_placeholder: self.none_placeholder,
+            fixed_column_widths: self.fixed_column_widths,
+            line_ending: self.line_ending,
+            ambiguous_wide: self.ambiguous_wide,
+            hidden_columns: self.hidden_columns,
+            strip_ansi: self.strip_ansi,
+            skip_empty_rows: self.skip_empty_rows,
+            title: self.title,
+            caption: self.caption,
+            header_separator_style: self.header_separator_style,
+            separate_columns: self.separate_columns,
+            control_chars: self.control_chars,
+            min_width: self.min_width,
+            padded_column: self.padded_column,
+            rtl: self.rtl,
+            trim_trailing_whitespace: self.trim_trailing_whitespace,
+            footer_separator_style: self.footer_separator_style,
+            no_color: self.no_color,
+        }
+    }
+}
+
+impl Default for TableBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::row::Row;
+    use crate::table_cell::string_width;
+    use crate::table_cell::{
+        Alignment, CenterBias, Color, TableCell, Truncation, VerticalAlignment, WrapMode, WrapStyle,
+    };
+    use crate::Borders;
+    use crate::ControlChars;
+    use crate::Layout;
+    use crate::LineEnding;
+    use crate::ParseError;
+    use crate::RowPosition;
+    use crate::Table;
+    use crate::TableBuilder;
+    use crate::TableStyle;
+    use pretty_assertions::assert_eq;
+    use std::io::{self, Write};
+
+    #[test]
+    fn correct_default_padding() {
+        let table = Table::builder()
+            .separate_rows(false)
+            .style(TableStyle::simple())
+            .rows(rows![
+                row![
+                    TableCell::builder("A").alignment(Alignment::Center),
+                    TableCell::builder("B").alignment(Alignment::Center),
+                ],
+                row![TableCell::builder("1"), TableCell::builder(1),],
+                row![TableCell::builder("2"), TableCell::builder(10),],
+                row![TableCell::builder("3"), TableCell::builder(100),],
+            ])
+            .build();
+
+        let expected = r"+---+-----+
+| A |  B  |
+| 1 | 1   |
+| 2 | 10  |
+| 3 | 100 |
++---+-----+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn uneven_center_alignment() {
+        let table = Table::builder()
+            .separate_rows(false)
+            .style(TableStyle::simple())
+            .rows(rows![
+                row![TableCell::builder("A").alignment(Alignment::Center),],
+                row![TableCell::builder(11),],
+                row![TableCell::builder(2),],
+                row![TableCell::builder(3),],
+            ])
+            .build();
+
+        let expected = r"+----+
+|  A |
+| 11 |
+| 2  |
+| 3  |
++----+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn uneven_center_alignment_2() {
+        let table = Table::builder()
+            .separate_rows(false)
+            .style(TableStyle::simple())
+            .rows(rows![row![
+                TableCell::builder("A1").alignment(Alignment::Center),
+                TableCell::builder("B").alignment(Alignment::Center),
+            ],])
+            .build();
+        println!("{}", table.render());
+        let expected = r"+----+---+
+| A1 | B |
++----+---+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn simple_table_style() {
+        let mut builder = TableBuilder::new().style(TableStyle::simple()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"+---------------------------------------------------------------------------------+
+|                            This is some centered text                           |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
++---------------------------------------------------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn uneven_with_varying_col_span() {
+        let table = Table::builder()
+            .separate_rows(true)
+            .style(TableStyle::simple())
+            .rows(rows![
+                row![
+                    TableCell::builder("A1111111").alignment(Alignment::Center),
+                    TableCell::builder("B").alignment(Alignment::Center),
+                ],
+                row![1, "1"],
+                row![2, "10"],
+                row![
+                    TableCell::builder(3)
+                        .alignment(Alignment::Left)
+                        .pad_content(false),
+                    "100",
+                ],
+                row![TableCell::builder("S")
+                    .alignment(Alignment::Center)
+                    .col_span(2),],
+            ])
+            .build();
+
+        let expected = "+----------+-----+
+| A1111111 |  B  |
++----------+-----+
+|        1 | 1   |
++----------+-----+
+|        2 | 10  |
++----------+-----+
+|\x003\0         | 100 |
++----------+-----+
+|        S       |
++----------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected.trim(), table.render().trim());
+    }
+
+    // TODO - The output of this test isn't ideal. There is probably a better way to calculate the
+    // the column/row layout that would improve this
+    #[test]
+    fn uneven_with_varying_col_span_2() {
+        let table = Table::builder()
+            .separate_rows(false)
+            .style(TableStyle::simple())
+            .rows(rows![
+                row![
+                    TableCell::builder("A").alignment(Alignment::Center),
+                    TableCell::builder("B").alignment(Alignment::Center),
+                ],
+                row![TableCell::builder(1), TableCell::builder(1),],
+                row![TableCell::builder(2), TableCell::builder(10),],
+                row![TableCell::builder(3), TableCell::builder(100),],
+                row![TableCell::builder("Spanner")
+                    .col_span(2)
+                    .alignment(Alignment::Center),],
+            ])
+            .build();
+
+        let expected = "+----+-----+
+|  A |  B  |
+| 1  | 1   |
+| 2  | 10  |
+| 3  | 100 |
+|  Spanner |
++----------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected.trim(), table.render().trim());
+    }
+
+    #[test]
+    fn extended_table_style_wrapped() {
+        let table = Table::builder()
+            .max_column_width(40)
+            .style(TableStyle::extended())
+            .max_column_widths(vec![(0, 1), (1, 1)].into_iter().collect())
+            .rows(rows![
+                row![
+                    TableCell::builder("This is some centered text").alignment(Alignment::Center).col_span(2),
+                ],
+                row![
+                    TableCell::builder("This is left aligned text"),
+                    TableCell::builder("This is right aligned text").alignment(Alignment::Right),
+                ],
+                row![
+                    TableCell::builder("This is left aligned text"),
+                    TableCell::builder("This is right aligned text").alignment(Alignment::Right),
+                ],
+                row![
+                    TableCell::builder("This is some really really really really really really really really really that is going to wrap to the next line\n1\n2").col_span(2),
+                ],
+            ])
+            .build();
+
+        let expected = r"╔═══════╗
+║ This  ║
+║ is so ║
+║ me ce ║
+║ ntere ║
+║ d tex ║
+║   t   ║
+╠═══╦═══╣
+║ T ║ T ║
+║ h ║ h ║
+║ i ║ i ║
+║ s ║ s ║
+║   ║   ║
+║ i ║ i ║
+║ s ║ s ║
+║   ║   ║
+║ l ║ r ║
+║ e ║ i ║
+║ f ║ g ║
+║ t ║ h ║
+║   ║ t ║
+║ a ║   ║
+║ l ║ a ║
+║ i ║ l ║
+║ g ║ i ║
+║ n ║ g ║
+║ e ║ n ║
+║ d ║ e ║
+║   ║ d ║
+║ t ║   ║
+║ e ║ t ║
+║ x ║ e ║
+║ t ║ x ║
+║   ║ t ║
+╠═══╬═══╣
+║ T ║ T ║
+║ h ║ h ║
+║ i ║ i ║
+║ s ║ s ║
+║   ║   ║
+║ i ║ i ║
+║ s ║ s ║
+║   ║   ║
+║ l ║ r ║
+║ e ║ i ║
+║ f ║ g ║
+║ t ║ h ║
+║   ║ t ║
+║ a ║   ║
+║ l ║ a ║
+║ i ║ l ║
+║ g ║ i ║
+║ n ║ g ║
+║ e ║ n ║
+║ d ║ e ║
+║   ║ d ║
+║ t ║   ║
+║ e ║ t ║
+║ x ║ e ║
+║ t ║ x ║
+║   ║ t ║
+╠═══╩═══╣
+║ This  ║
+║ is so ║
+║ me re ║
+║ ally  ║
+║ reall ║
+║ y rea ║
+║ lly r ║
+║ eally ║
+║  real ║
+║ ly re ║
+║ ally  ║
+║ reall ║
+║ y rea ║
+║ lly r ║
+║ eally ║
+║  that ║
+║  is g ║
+║ oing  ║
+║ to wr ║
+║ ap to ║
+║  the  ║
+║ next  ║
+║ line  ║
+║ 1     ║
+║ 2     ║
+╚═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn elegant_table_style() {
+        let mut builder = Table::builder().style(TableStyle::elegant()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"╓─────────────────────────────────────────────────────────────────────────────────╖
+║                            This is some centered text                           ║
+╟────────────────────────────────────────╥────────────────────────────────────────╢
+║ This is left aligned text              ║             This is right aligned text ║
+╟────────────────────────────────────────╫────────────────────────────────────────╢
+║ This is left aligned text              ║             This is right aligned text ║
+╟────────────────────────────────────────╨────────────────────────────────────────╢
+║ This is some really really really really really really really really really tha ║
+║ t is going to wrap to the next line                                             ║
+╙─────────────────────────────────────────────────────────────────────────────────╜
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn thin_table_style() {
+        let mut builder = Table::builder().style(TableStyle::thin()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"┌─────────────────────────────────────────────────────────────────────────────────┐
+│                            This is some centered text                           │
+├────────────────────────────────────────┬────────────────────────────────────────┤
+│ This is left aligned text              │             This is right aligned text │
+├────────────────────────────────────────┼────────────────────────────────────────┤
+│ This is left aligned text              │             This is right aligned text │
+├────────────────────────────────────────┴────────────────────────────────────────┤
+│ This is some really really really really really really really really really tha │
+│ t is going to wrap to the next line                                             │
+└─────────────────────────────────────────────────────────────────────────────────┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn rounded_table_style() {
+        let mut builder = Table::builder().style(TableStyle::rounded()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"╭─────────────────────────────────────────────────────────────────────────────────╮
+│                            This is some centered text                           │
+├────────────────────────────────────────┬────────────────────────────────────────┤
+│ This is left aligned text              │             This is right aligned text │
+├────────────────────────────────────────┼────────────────────────────────────────┤
+│ This is left aligned text              │             This is right aligned text │
+├────────────────────────────────────────┴────────────────────────────────────────┤
+│ This is some really really really really really really really really really tha │
+│ t is going to wrap to the next line                                             │
+╰─────────────────────────────────────────────────────────────────────────────────╯
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn heavy_table_style() {
+        let mut builder = Table::builder().style(TableStyle::heavy()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓
+┃                            This is some centered text                           ┃
+┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┳━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+┃ This is left aligned text              ┃             This is right aligned text ┃
+┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━╋━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+┃ This is left aligned text              ┃             This is right aligned text ┃
+┣━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┻━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┫
+┃ This is some really really really really really really really really really tha ┃
+┃ t is going to wrap to the next line                                             ┃
+┗━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┛
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn double_table_style() {
+        let mut builder = Table::builder().style(TableStyle::double()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"╔═════════════════════════════════════════════════════════════════════════════════╗
+║                            This is some centered text                           ║
+╠════════════════════════════════════════╦════════════════════════════════════════╣
+║ This is left aligned text              ║             This is right aligned text ║
+╠════════════════════════════════════════╬════════════════════════════════════════╣
+║ This is left aligned text              ║             This is right aligned text ║
+╠════════════════════════════════════════╩════════════════════════════════════════╣
+║ This is some really really really really really really really really really tha ║
+║ t is going to wrap to the next line                                             ║
+╚═════════════════════════════════════════════════════════════════════════════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn ascii_table_style() {
+        let mut builder = Table::builder().style(TableStyle::ascii()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"+---------------------------------------------------------------------------------+
+|                            This is some centered text                           |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
++---------------------------------------------------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn psql_table_style() {
+        let table = Table::builder()
+            .style(TableStyle::psql())
+            .borders(Borders::INNER_VERTICAL)
+            .header_alignment(Alignment::Center)
+            .header(row!["Name", "Value"])
+            .rows(rows![row!["Alpha", "1"], row!["Beta", "2"]])
+            .build();
+
+        let expected = r"  Name | Value 
+-------+-------
+ Alpha | 1     
+ Beta  | 2     
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn markdown_table_style() {
+        let mut builder = Table::builder()
+            .style(TableStyle::markdown())
+            .has_top_boarder(false)
+            .has_bottom_boarder(false)
+            .to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"|                            This is some centered text                           |
+|----------------------------------------|----------------------------------------|
+| This is left aligned text              |             This is right aligned text |
+|----------------------------------------|----------------------------------------|
+| This is left aligned text              |             This is right aligned text |
+|----------------------------------------|----------------------------------------|
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn complex_table() {
+        let mut table = Table::builder()
+            .rows(rows![
+                row![
+                    TableCell::builder("Col*1*Span*2").col_span(2),
+                    "Col 2 Span 1",
+                    TableCell::builder("Col 3 Span 2").col_span(2),
+                    "Col 4 Span 1"
+                ],
+                row![
+                    "Col 1 Span 1",
+                    "Col 2 Span 1",
+                    "Col 3 Span 1",
+                    TableCell::builder("Col 4 Span 2").col_span(2)
+                ],
+                row!["fasdaff", "fff", "fff",],
+                row![
+                    TableCell::builder("fasdff")
+                        .col_span(3)
+                        .alignment(Alignment::Right),
+                    TableCell::builder("fffdff").col_span(4),
+                ],
+                row!["fasdsaff", "fff", "f\nf\nf\nfff\nrrr\n\n\n",],
+                row!["fasdsaff",],
+            ])
+            .build();
+
+        let s = table.render();
+
+        table.add_row(row![TableCell::builder(s)
+            .col_span(3)
+            .alignment(Alignment::Left)]);
+
+        let expected = r"╔═════════════════════════════════════════════════════╦══════════════════════════╦════════════════╦══════════════╗
+║ Col*1*Span*2                                        ║ Col 2 Span 1             ║ Col 3 Span 2   ║ Col 4 Span 1 ║
+╠══════════════════════════╦══════════════════════════╬══════════════════════════╬════════════════╬══════════════╣
+║ Col 1 Span 1             ║ Col 2 Span 1             ║ Col 3 Span 1             ║ Col 4 Span 2   ║              ║
+╠══════════════════════════╬══════════════════════════╬══════════════════════════╬═══════╦════════╬══════════════╣
+║ fasdaff                  ║ fff                      ║ fff                      ║       ║        ║              ║
+╠══════════════════════════╩══════════════════════════╩══════════════════════════╬═══════╩════════╩══════════════╣
+║                                                                         fasdff ║ fffdff                        ║
+╠══════════════════════════╦══════════════════════════╦══════════════════════════╬═══════╦════════╦══════════════╣
+║ fasdsaff                 ║ fff                      ║ f                        ║       ║        ║              ║
+║                          ║                          ║ f                        ║       ║        ║              ║
+║                          ║                          ║ f                        ║       ║        ║              ║
+║                          ║                          ║ fff                      ║       ║        ║              ║
+║                          ║                          ║ rrr                      ║       ║        ║              ║
+║                          ║                          ║                          ║       ║        ║              ║
+║                          ║                          ║                          ║       ║        ║              ║
+║                          ║                          ║                          ║       ║        ║              ║
+╠══════════════════════════╬══════════════════════════╬══════════════════════════╬═══════╬════════╬══════════════╣
+║ fasdsaff                 ║                          ║                          ║       ║        ║              ║
+╠══════════════════════════╩══════════════════════════╩══════════════════════════╬═══════╬════════╬══════════════╣
+║ ╔═════════════════════════════╦══════════════╦════════════════╦══════════════╗ ║       ║        ║              ║
+║ ║ Col*1*Span*2                ║ Col 2 Span 1 ║ Col 3 Span 2   ║ Col 4 Span 1 ║ ║       ║        ║              ║
+║ ╠══════════════╦══════════════╬══════════════╬════════════════╬══════════════╣ ║       ║        ║              ║
+║ ║ Col 1 Span 1 ║ Col 2 Span 1 ║ Col 3 Span 1 ║ Col 4 Span 2   ║              ║ ║       ║        ║              ║
+║ ╠══════════════╬══════════════╬══════════════╬═══════╦════════╬══════════════╣ ║       ║        ║              ║
+║ ║ fasdaff      ║ fff          ║ fff          ║       ║        ║              ║ ║       ║        ║              ║
+║ ╠══════════════╩══════════════╩══════════════╬═══════╩════════╩══════════════╣ ║       ║        ║              ║
+║ ║                                     fasdff ║ fffdff                        ║ ║       ║        ║              ║
+║ ╠══════════════╦══════════════╦══════════════╬═══════╦════════╦══════════════╣ ║       ║        ║              ║
+║ ║ fasdsaff     ║ fff          ║ f            ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║ f            ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║ f            ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║ fff          ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║ rrr          ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║              ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║              ║       ║        ║              ║ ║       ║        ║              ║
+║ ║              ║              ║              ║       ║        ║              ║ ║       ║        ║              ║
+║ ╠══════════════╬══════════════╬══════════════╬═══════╬════════╬══════════════╣ ║       ║        ║              ║
+║ ║ fasdsaff     ║              ║              ║       ║        ║              ║ ║       ║        ║              ║
+║ ╚══════════════╩══════════════╩══════════════╩═══════╩════════╩══════════════╝ ║       ║        ║              ║
+║                                                                                ║       ║        ║              ║
+╚════════════════════════════════════════════════════════════════════════════════╩═══════╩════════╩══════════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn no_top_boarder() {
+        let mut builder = Table::builder()
+            .style(TableStyle::simple())
+            .has_top_boarder(false)
+            .to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"|                            This is some centered text                           |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
++---------------------------------------------------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn no_bottom_boarder() {
+        let mut builder = Table::builder()
+            .style(TableStyle::simple())
+            .has_bottom_boarder(false)
+            .to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"+---------------------------------------------------------------------------------+
+|                            This is some centered text                           |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn no_separators() {
+        let mut builder = Table::builder()
+            .style(TableStyle::simple())
+            .separate_rows(false)
+            .to_owned();
+
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"+---------------------------------------------------------------------------------+
+|                            This is some centered text                           |
+| This is left aligned text              |             This is right aligned text |
+| This is left aligned text              |             This is right aligned text |
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
++---------------------------------------------------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn some_rows_no_separators() {
+        let mut builder = Table::builder().style(TableStyle::simple()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let mut table = builder.build();
+
+        table.rows[2].has_separator = false;
+
+        let expected = r"+---------------------------------------------------------------------------------+
+|                            This is some centered text                           |
++----------------------------------------+----------------------------------------+
+| This is left aligned text              |             This is right aligned text |
+| This is left aligned text              |             This is right aligned text |
++----------------------------------------+----------------------------------------+
+| This is some really really really really really really really really really tha |
+| t is going to wrap to the next line                                             |
++---------------------------------------------------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn colored_data_works() {
+        let table = Table::builder()
+            .rows(rows![row![TableCell::builder("\u{1b}[31ma\u{1b}[0m")]])
+            .build();
+        let expected = "╔═══╗
+║ \u{1b}[31ma\u{1b}[0m ║
+╚═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn verbatim_cell_keeps_tabs() {
+        let cell = TableCell::verbatim("a\tb\n\tc");
+        assert_eq!(vec!["a\tb", "\tc"], cell.wrapped_content(100));
+
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row![TableCell::verbatim("a\tb")]])
+            .build();
+        assert_eq!("+--+\n|a\tb|\n+--+\n", table.render());
+    }
+
+    #[test]
+    fn control_characters_have_no_width() {
+        assert_eq!(2, string_width("a\0b"));
+        assert_eq!(2, string_width("a\u{7}b\r"));
+        assert_eq!(2, string_width("\u{1b}[31mab\u{1b}[0m"));
+
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row!["a\0b", "c"], row!["ab", "c"]])
+            .build();
+        let lines = table.to_lines();
+        assert_eq!("| ab | c |", lines[1].replace('\0', ""));
+        assert_eq!("| ab | c |", lines[3]);
+    }
+
+    #[test]
+    fn verbatim_cell_is_unchanged() {
+        let art = "+--+\n|  |\n+--+";
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .max_column_width(2)
+            .rows(rows![row!["A", TableCell::verbatim(art)]])
+            .build();
+
+        let expected = r"+---+----+
+| A |+--+|
+|   ||  ||
+|   |+--+|
++---+----+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn vertical_alignment() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row![
+                "1\n2\n3\n4\n5",
+                TableCell::builder("Top"),
+                TableCell::builder("Middle").vertical_alignment(VerticalAlignment::Middle),
+                TableCell::builder("Bottom").vertical_alignment(VerticalAlignment::Bottom),
+            ]])
+            .build();
+
+        let expected = r"+---+-----+--------+--------+
+| 1 | Top |        |        |
+| 2 |     |        |        |
+| 3 |     | Middle |        |
+| 4 |     |        |        |
+| 5 |     |        | Bottom |
++---+-----+--------+--------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn vertical_alignment_middle_even_height() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row![
+                "1\n2\n3\n4",
+                TableCell::builder("Total").vertical_alignment(VerticalAlignment::Middle),
+            ]])
+            .build();
+
+        let expected = r"+---+-------+
+| 1 |       |
+| 2 | Total |
+| 3 |       |
+| 4 |       |
++---+-------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn row_span() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![
+                row![TableCell::builder("Span").row_span(2), "B"],
+                row!["C"],
+            ])
+            .build();
+
+        let expected = r"+------+---+
+| Span | B |
+|      +---+
+|      | C |
++------+---+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn row_span_tall_content() {
+        let table = Table::builder()
+            .style(TableStyle::thin())
+            .rows(rows![
+                row!["A", TableCell::builder("1\n2\n3").row_span(2), "B"],
+                row!["C", "D"],
+                row!["E", "F", "G"],
+            ])
+            .build();
+
+        let expected = r"┌───┬───┬───┐
+│ A │ 1 │ B │
+├───┤   ├───┤
+│ C │ 2 │ D │
+│   │ 3 │   │
+├───┼───┼───┤
+│ E │ F │ G │
+└───┴───┴───┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn truncate_wrap_mode() {
+        let text = "abcdefghijklmnopqrstuvwxyz0123";
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .max_column_width(10)
+            .rows(rows![row![
+                TableCell::builder(text).wrap_mode(WrapMode::Truncate)
+            ]])
+            .build();
+
+        let expected = r"+----------+
+| abcdefg… |
++----------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let unpadded = TableCell::builder(text)
+            .wrap_mode(WrapMode::Truncate)
+            .pad_content(false)
+            .build();
+        assert_eq!(
+            vec!["\0abcdefghi…\0".to_string()],
+            unpadded.wrapped_content(10)
+        );
+    }
+
+    #[test]
+    fn truncate_wrap_mode_ignores_ansi() {
+        let cell = TableCell::builder("\u{1b}[31mabcdefghijklmnopqrstuvwxyz0123\u{1b}[0m")
+            .wrap_mode(WrapMode::Truncate)
+            .build();
+        assert_eq!(
+            vec![" \u{1b}[31mabcdefg…\u{1b}[0m ".to_string()],
+            cell.wrapped_content(10)
+        );
+    }
+
+    #[test]
+    fn no_wrap_mode() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .max_column_width(10)
+            .rows(rows![
+                row![TableCell::builder("abcdefghijklmnopqrstuvwxyz0123")
+                    .wrap_mode(WrapMode::NoWrap)],
+                row!["abc"],
+            ])
+            .build();
+
+        let expected = r"+--------------------------------+
+| abcdefghijklmnopqrstuvwxyz0123 |
++--------------------------------+
+| abc                            |
++--------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn word_wrap_style() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .max_column_width(40)
+            .rows(rows![
+                row![TableCell::builder("This is some really really really really really really really really really that is going to wrap to the next line")
+                    .alignment(Alignment::Center)
+                    .wrap_style(WrapStyle::Word)],
+                row![TableCell::builder("Supercalifragilisticexpialidociouslyexpialidocious\nword")
+                    .wrap_style(WrapStyle::Word)],
+            ])
+            .build();
+
+        let expected = r"+----------------------------------------+
+|    This is some really really really   |
+|   really really really really really   |
+|   really that is going to wrap to the  |
+|                next line               |
++----------------------------------------+
+| Supercalifragilisticexpialidociouslyex |
+| pialidocious                           |
+| word                                   |
++----------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn render_to_writer() {
+        let mut builder = Table::builder().style(TableStyle::thin()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let mut buffer = Vec::new();
+        table.render_to(&mut buffer).unwrap();
+        assert_eq!(table.render(), String::from_utf8(buffer).unwrap());
+    }
+
+    #[test]
+    fn render_to_propagates_errors() {
+        struct FailingWriter;
+
+        impl Write for FailingWriter {
+            fn write(&mut self, _: &[u8]) -> io::Result<usize> {
+                Err(io::Error::other("failed"))
+            }
+
+            fn flush(&mut self) -> io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let table = Table::builder().rows(rows![row!["A"]]).build();
+        let error = table.render_to(&mut FailingWriter).unwrap_err();
+        assert_eq!(io::ErrorKind::Other, error.kind());
+    }
+
+    #[test]
+    fn fit_to_width() {
+        let mut builder = Table::builder().style(TableStyle::simple()).to_owned();
+        add_data_to_test_table(&mut builder);
+        let mut table = builder.build();
+        table.fit_to_width(41);
+
+        let expected = r"+---------------------------------------+
+|       This is some centered text      |
++-------------------+-------------------+
+| This is left alig | This is right ali |
+| ned text          |         gned text |
++-------------------+-------------------+
+| This is left alig | This is right ali |
+| ned text          |         gned text |
++-------------------+-------------------+
+| This is some really really really rea |
+| lly really really really really reall |
+| y that is going to wrap to the next l |
+| ine                                   |
++---------------------------------------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn column_alignment() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .column_alignment(1, Alignment::Right)
+            .rows(rows![
+                row![
+                    "Name",
+                    TableCell::builder("Value").alignment(Alignment::Center)
+                ],
+                row!["A", "1"],
+                row!["B", "100000"],
+                row!["C", TableCell::builder("2").alignment(Alignment::Left)],
+            ])
+            .build();
+
+        let expected = r"+------+--------+
+| Name |  Value |
++------+--------+
+| A    |      1 |
++------+--------+
+| B    | 100000 |
++------+--------+
+| C    | 2      |
++------+--------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn header() {
+        let table = Table::builder()
+            .style(TableStyle::thin())
+            .separate_rows(false)
+            .header(row!["Name", "Value"])
+            .header_horizontal('═')
+            .header_alignment(Alignment::Center)
+            .rows(rows![row!["Width", 10], row!["Height", 200]])
+            .build();
+
+        let expected = r"┌────────┬───────┐
+│  Name  │ Value │
+╞════════╪═══════╡
+│ Width  │    10 │
+│ Height │   200 │
+└────────┴───────┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let table = Table::builder()
+            .style(TableStyle::thin())
+            .header(row!["Name", "Value"])
+            .header_horizontal('━')
+            .rows(rows![row![TableCell::builder("Size").col_span(2)]])
+            .build();
+
+        let expected = r"┌──────┬───────┐
+│ Name │ Value │
+┝━━━━━━┷━━━━━━━┥
+│ Size         │
+└──────────────┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn set_header() {
+        let mut table = Table::builder()
+            .style(TableStyle::simple())
+            .separate_rows(false)
+            .rows(rows![row!["A", 1], row!["B", 2]])
+            .build();
+        table.set_header(row!["Old", "Header"]);
+        table.set_header(row!["Key", "Value"]);
+
+        let expected = r"+-----+-------+
+| Key | Value |
++-----+-------+
+| A   |     1 |
+| B   |     2 |
++-----+-------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn render_markdown() {
+        let table = Table::builder()
+            .column_alignment(2, Alignment::Right)
+            .rows(rows![
+                row!["Name", "Quantity", "Price"],
+                row![
+                    "\u{1b}[31mApple\u{1b}[0m",
+                    TableCell::builder(3).alignment(Alignment::Center),
+                    "1.50"
+                ],
+                row!["Banana | Split", TableCell::builder("12").col_span(2)],
+            ])
+            .build();
+
+        let expected = r"| Name            | Quantity | Price |
+| --------------- | :------: | ----: |
+| Apple           | 3        | 1.50  |
+| Banana \| Split | 12       |       |
+";
+        println!("{}", table.render_markdown());
+        assert_eq!(expected, table.render_markdown());
+    }
+
+    #[test]
+    fn column_widths() {
+        let mut builder = Table::builder().to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+        assert_eq!(vec![40, 40], table.column_widths());
+
+        let table = Table::builder()
+            .max_column_widths(vec![(1, 5)].into_iter().collect())
+            .rows(rows![
+                row!["A", "Some long text", "C"],
+                row![TableCell::builder("Spanning two columns").col_span(2)],
+            ])
+            .build();
+        assert_eq!(vec![11, 5, 3], table.column_widths());
+    }
+
+    #[test]
+    fn cell_at_column() {
+        let mut row = row![TableCell::builder("A").col_span(2), "B"];
+        assert_eq!("A", row.cell_at_column(0).unwrap().data);
+        assert_eq!("A", row.cell_at_column(1).unwrap().data);
+        assert_eq!("B", row.cell_at_column(2).unwrap().data);
+        assert!(row.cell_at_column(3).is_none());
+
+        row.cell_at_column_mut(2).unwrap().data = "C".to_string();
+        assert_eq!("C", row.cells[1].data);
+    }
+
+    #[test]
+    fn render_to_width() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .max_column_widths(vec![(2, 10)].into_iter().collect())
+            .rows(rows![row![
+                "This is some text",
+                "This is some much longer text that will need to be wrapped",
+                "This is some text that won't fit"
+            ]])
+            .build();
+
+        assert_eq!(vec![11, 35, 10], table.proportional_column_widths(60));
+        let rendered = table.render_to_width(60);
+        println!("{}", rendered);
+        assert!(rendered.lines().all(|line| string_width(line) == 60));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let mut builder = Table::builder()
+            .style(
+                TableStyle::builder()
+                    .based_on(TableStyle::rounded())
+                    .horizontal_fill("─┄")
+                    .build(),
+            )
+            .max_column_widths(vec![(0, 10), (1, 20)].into_iter().collect())
+            .column_alignment(1, Alignment::Right)
+            .to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let json = serde_json::to_string(&table).unwrap();
+        assert!(json.contains(r#""alignment":"center""#));
+        assert!(json.contains(r#""horizontal_fill":"─┄""#));
+        let deserialized: Table = serde_json::from_str(&json).unwrap();
+        assert_eq!(
+            serde_json::to_value(&table).unwrap(),
+            serde_json::to_value(&deserialized).unwrap()
+        );
+        assert_eq!(table.render(), deserialized.render());
+    }
+
+    #[test]
+    fn collect_and_extend_rows() {
+        let mut table: Table = (1..=3).map(|i| row![i, i * 10]).collect();
+        table.extend(vec![row!["A", "B"]]);
+
+        let expected = r"╔═══╦════╗
+║ 1 ║ 10 ║
+╠═══╬════╣
+║ 2 ║ 20 ║
+╠═══╬════╣
+║ 3 ║ 30 ║
+╠═══╬════╣
+║ A ║ B  ║
+╚═══╩════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn numbers_are_right_aligned() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![
+                row!["Item", "Cost"],
+                row!["Apple", 1.5],
+                row!["Melon", 12],
+                row!["Total", 13.5],
+            ])
+            .build();
+
+        let expected = r"+-------+------+
+| Item  | Cost |
++-------+------+
+| Apple |  1.5 |
++-------+------+
+| Melon |   12 |
++-------+------+
+| Total | 13.5 |
++-------+------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn cells_from_strings_are_left_aligned() {
+        let path = std::path::Path::new("/tmp");
+        let row = row![
+            std::borrow::Cow::Borrowed("a"),
+            Box::<str>::from("b"),
+            TableCell::new(path.display())
+        ];
+        assert_eq!(
+            vec!["a", "b", "/tmp"],
+            row.cells.iter().map(|c| &c.data).collect::<Vec<_>>()
+        );
+        for cell in &row.cells {
+            assert_eq!(Alignment::Left, cell.alignment);
+            assert!(cell.inherit_alignment);
+        }
+    }
+
+    #[test]
+    fn colors_are_carried_across_wrapped_lines() {
+        let cell = TableCell::new("\u{1b}[31mabcdefghijklmnopqrstu\u{1b}[0m");
+        let wrapped = cell.wrapped_content(9);
+        assert_eq!(3, wrapped.len());
+        for line in wrapped {
+            let content = line.trim();
+            assert!(content.starts_with("\u{1b}[31m"), "{:?}", line);
+            assert!(content.ends_with("\u{1b}[0m"), "{:?}", line);
+        }
+    }
+
+    #[test]
+    fn hyperlinks_are_not_counted() {
+        let link = "\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\";
+        let cell = TableCell::new(link);
+        assert_eq!(6, cell.width());
+        assert_eq!(4, string_width(link));
+
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row![link], row!["abcdef"]])
+            .build();
+        let expected = format!(
+            "+--------+
+| {}   |
++--------+
+| abcdef |
++--------+
+",
+            link
+        );
+        assert_eq!(expected, table.render());
+
+        let wrapped = TableCell::new(link).wrapped_content(4);
+        assert_eq!(
+            vec![
+                " \u{1b}]8;;https://example.com\u{1b}\\li ",
+                " nk\u{1b}]8;;\u{1b}\\ "
+            ],
+            wrapped
+        );
+    }
+
+    #[test]
+    fn table_style_builder() {
+        let style = TableStyle::builder()
+            .based_on(TableStyle::thin())
+            .vertical('┃')
+            .build();
+
+        let table = Table::builder()
+            .style(style)
+            .rows(rows![row!["A", "B"], row!["C", "D"]])
+            .build();
+
+        let expected = r"┌───┬───┐
+┃ A ┃ B ┃
+├───┼───┤
+┃ C ┃ D ┃
+└───┴───┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn padding() {
+        let table = Table::builder()
+            .rows(rows![
+                row![TableCell::builder("A").padding(3), "B"],
+                row!["C", TableCell::builder("Wrapped").padding(3)]
+            ])
+            .max_column_width(10)
+            .build();
+
+        let expected = r"╔═══════╦══════════╗
+║   A   ║ B        ║
+╠═══════╬══════════╣
+║ C     ║   Wrap   ║
+║       ║   ped    ║
+╚═══════╩══════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn pad_content_overrides_padding() {
+        let mut cell = TableCell::builder("A").padding(3).build();
+        cell.pad_content = false;
+        let table = Table::builder().rows(rows![row![cell, "B"]]).build();
+
+        let expected = "╔═╦═══╗
+║\0A\0║ B ║
+╚═╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn asymmetric_padding() {
+        let table = Table::builder()
+            .rows(rows![
+                row![
+                    TableCell::builder("Label").left_padding(2).right_padding(0),
+                    "B"
+                ],
+                row!["C", "D"]
+            ])
+            .build();
+
+        let expected = "╔═══════╦═══╗
+║  Label\0║ B ║
+╠═══════╬═══╣
+║ C     ║ D ║
+╚═══════╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn grapheme_clusters() {
+        let table = Table::builder()
+            .rows(rows![row!["👨‍👩‍👧", "e\u{301}te\u{301}"], row!["ab", "abc"]])
+            .build();
+
+        let expected = "╔════╦═════╗
+║ 👨‍👩‍👧 ║ e\u{301}te\u{301} ║
+╠════╬═════╣
+║ ab ║ abc ║
+╚════╩═════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn grapheme_clusters_are_not_split() {
+        let cell = TableCell::new("e\u{301}e\u{301}e\u{301}");
+        assert_eq!(5, cell.width());
+        assert_eq!(
+            vec![" e\u{301}e\u{301} ", " e\u{301} "],
+            cell.wrapped_content(4)
+        );
+    }
+
+    #[test]
+    fn justify_alignment() {
+        let table = Table::builder()
+            .rows(rows![
+                row![TableCell::builder("one two three four five")
+                    .alignment(Alignment::Justify)
+                    .wrap_style(WrapStyle::Word)],
+                row!["0123456789abcdef"]
+            ])
+            .max_column_width(18)
+            .build();
+
+        let expected = r"╔══════════════════╗
+║ one   two  three ║
+║ four five        ║
+╠══════════════════╣
+║ 0123456789abcdef ║
+╚══════════════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn insert_row() {
+        let mut table = Table::builder().rows(rows![row!["B"]]).build();
+        table.insert_row(0, row!["A"]);
+        table.insert_row(2, row!["D"]);
+        table.insert_row(2, row!["C"]);
+        table.insert_row(100, row!["E"]);
+
+        let data: Vec<&str> = table
+            .rows
+            .iter()
+            .map(|row| row.cells[0].data.as_str())
+            .collect();
+        assert_eq!(vec!["A", "B", "C", "D", "E"], data);
+
+        let mut table = Table::builder()
+            .header(row!["Header"])
+            .rows(rows![row!["B"]])
+            .build();
+        table.insert_row(0, row!["A"]);
+        table.insert_row(3, row!["C"]);
+
+        let data: Vec<&str> = table
+            .rows
+            .iter()
+            .map(|row| row.cells[0].data.as_str())
+            .collect();
+        assert_eq!(vec!["Header", "A", "B", "C"], data);
+        assert!(table.has_header);
+    }
+
+    #[test]
+    fn remove_row() {
+        let mut table = Table::builder()
+            .header(row!["Header"])
+            .rows(rows![row!["A"], row!["B"]])
+            .build();
+
+        assert_eq!("B", table.remove_row(2).unwrap().cells[0].data);
+        assert!(table.remove_row(2).is_none());
+        assert_eq!("Header", table.remove_row(0).unwrap().cells[0].data);
+        assert!(!table.has_header);
+
+        table.clear();
+        assert!(table.rows.is_empty());
+    }
+
+    #[test]
+    fn transpose() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row!["Name", "Age"], row!["Alice", 31], row!["Bob"]])
+            .build();
+
+        let expected = r"+------+-------+-----+
+| Name | Alice | Bob |
++------+-------+-----+
+| Age  |    31 |     |
++------+-------+-----+
+";
+        println!("{}", table.transpose().render());
+        assert_eq!(expected, table.transpose().render());
+    }
+
+    #[test]
+    fn cell_colors() {
+        let table = Table::builder()
+            .max_column_width(6)
+            .rows(rows![row![
+                TableCell::builder("abcdefgh").fg(Color::Green),
+                TableCell::builder("x").bg(Color::Fixed(8))
+            ]])
+            .build();
+
+        let expected = "╔══════╦═══╗
+║\u{1b}[32m abcd \u{1b}[0m║\u{1b}[48;5;8m x \u{1b}[0m║
+║\u{1b}[32m efgh \u{1b}[0m║   ║
+╚══════╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn no_color() {
+        let link = "\u{1b}]8;;https://example.com\u{1b}\\link\u{1b}]8;;\u{1b}\\";
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .no_color(true)
+            .rows(rows![row![
+                TableCell::builder("green").fg(Color::Green),
+                link
+            ]])
+            .build();
+
+        let expected = format!(
+            "+-------+------+
+| green | {} |
++-------+------+
+",
+            link
+        );
+        assert_eq!(expected, table.render());
+
+        #[cfg(feature = "terminal")]
+        {
+            let no_color = matches!(std::env::var_os("NO_COLOR"), Some(value) if !value.is_empty());
+            assert_eq!(
+                no_color,
+                Table::builder().respect_no_color().build().no_color
+            );
+        }
+    }
+
+    #[test]
+    fn render_plain() {
+        let table = Table::builder()
+            .header(row!["Name", "Score"])
+            .rows(rows![
+                row!["\u{1b}[31mAlice\u{1b}[0m", 10],
+                row![TableCell::builder("Bob").fg(Color::Green), 7]
+            ])
+            .build();
+
+        let expected = r" Name    Score
+ Alice      10
+ Bob         7
+";
+        println!("{}", table.render_plain());
+        assert_eq!(expected, table.render_plain());
+    }
+
+    #[test]
+    fn render_plain_without_left_border() {
+        let mut table = Table::builder()
+            .rows(rows![row![
+                TableCell::builder("é").padding(0),
+                TableCell::builder("x").padding(0)
+            ]])
+            .build();
+        table.has_left_border = false;
+
+        assert_eq!("é x\n", table.render_plain());
+    }
+
+    #[test]
+    fn tabs_are_expanded() {
+        assert_eq!(vec![" a   b "], TableCell::new("a\tb").wrapped_content(100));
+        assert_eq!(
+            vec![" abcd    e "],
+            TableCell::new("abcd\te").wrapped_content(100)
+        );
+        assert_eq!(
+            vec![" a b "],
+            TableCell::builder("a\tb")
+                .tab_width(2)
+                .build()
+                .wrapped_content(100)
+        );
+    }
+
+    #[test]
+    fn max_column_width_smaller_than_padding() {
+        let table = Table::builder()
+            .max_column_width(1)
+            .rows(rows![row!["abc", "d"]])
+            .build();
+
+        let expected = r"╔═══╦═══╗
+║ a ║ d ║
+║ b ║   ║
+║ c ║   ║
+╚═══╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        // Widths smaller than the padding can still be requested directly
+        let content: String = TableCell::new("abc").wrapped_content(0).concat();
+        assert_eq!("abc", content.replace(' ', ""));
+    }
+
+    #[test]
+    fn col_span_larger_than_columns() {
+        let row = Row::new(vec![TableCell::builder("spans").col_span(5)]);
+        assert_eq!("| spans |", row.format(&[7], &TableStyle::simple()));
+
+        let table = Table::builder()
+            .rows(rows![row![TableCell::builder("spans").col_span(5)]])
+            .build();
+        let expected = r"╔═══════╗
+║ spans ║
+╚═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let table = Table::builder()
+            .rows(rows![
+                row!["a", TableCell::builder("spans").col_span(5)],
+                row!["b", "c"]
+            ])
+            .build();
+        let expected = r"╔═══╦═══════╗
+║ a ║ spans ║
+╠═══╬═══════╣
+║ b ║ c     ║
+╚═══╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(2, table.column_count());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn zero_col_span() {
+        let cell = TableCell::builder("a").col_span(0).build();
+        assert_eq!(1, cell.col_span);
+
+        let render = |cell: TableCell| {
+            Table::builder()
+                .rows(rows![row![cell, "b"], row!["c", "d"]])
+                .build()
+                .render()
+        };
+        assert_eq!(
+            render(TableCell::new("a")),
+            render(TableCell::builder("a").col_span(0).build())
+        );
+
+        let mut cell = TableCell::new("a");
+        cell.col_span = 0;
+        assert_eq!(render(TableCell::new("a")), render(cell.clone()));
+        assert_eq!(
+            "| a | b |",
+            Row::new(vec![cell, TableCell::new("b")]).format(&[3, 3], &TableStyle::simple())
+        );
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn derive_table_row() {
+        use crate::TableRow;
+
+        #[derive(TableRow)]
+        struct Fruit {
+            name: &'static str,
+            #[table(rename = "Qty", align = "center")]
+            quantity: u32,
+            price: f64,
+        }
+
+        let apple = Fruit {
+            name: "Apple",
+            quantity: 3,
+            price: 0.5,
+        };
+        let cells: Vec<String> = apple.to_row().cells.into_iter().map(|c| c.data).collect();
+        assert_eq!(vec!["Apple", "3", "0.5"], cells);
+
+        let table = Table::from_rows(&[
+            apple,
+            Fruit {
+                name: "Banana",
+                quantity: 12,
+                price: 0.25,
+            },
+        ]);
+        let expected = r"╔════════╦═════╦═══════╗
+║ name   ║ Qty ║ price ║
+╠════════╬═════╬═══════╣
+║ Apple  ║  3  ║   0.5 ║
+╠════════╬═════╬═══════╣
+║ Banana ║  12 ║  0.25 ║
+╚════════╩═════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[cfg(feature = "serde_json")]
+    #[test]
+    fn from_json() {
+        let values: Vec<serde_json::Value> = serde_json::from_str(
+            r#"[
+                {"age": 31, "name": "Alice"},
+                {"email": "bob@example.com", "name": "Bob"},
+                {"active": true, "age": 47, "tags": ["admin", "ops"]}
+            ]"#,
+        )
+        .unwrap();
+        let table = Table::from_json(&values).unwrap();
+
+        let expected = r#"╔═════╦═══════╦═════════════════╦════════╦═════════════════╗
+║ age ║ name  ║ email           ║ active ║ tags            ║
+╠═════╬═══════╬═════════════════╬════════╬═════════════════╣
+║  31 ║ Alice ║                 ║        ║                 ║
+╠═════╬═══════╬═════════════════╬════════╬═════════════════╣
+║     ║ Bob   ║ bob@example.com ║        ║                 ║
+╠═════╬═══════╬═════════════════╬════════╬═════════════════╣
+║  47 ║       ║                 ║ true   ║ ["admin","ops"] ║
+╚═════╩═══════╩═════════════════╩════════╩═════════════════╝
+"#;
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        assert_eq!(
+            Err(crate::JsonError::NotAnObject(1)),
+            Table::from_json(&[serde_json::json!({}), serde_json::json!(1)]).map(|_| ())
+        );
+    }
+
+    #[test]
+    fn render_pages() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .header(row!["Name", "Qty"])
+            .rows(rows![
+                row!["Apple", 3],
+                row!["Banana", 12],
+                row!["Cherry", 100],
+                row!["Date", 7],
+                row!["Elderberry", 42]
+            ])
+            .build();
+
+        let pages = table.render_pages(2);
+        let expected = vec![
+            r"+------------+-----+
+| Name       | Qty |
++------------+-----+
+| Apple      |   3 |
++------------+-----+
+| Banana     |  12 |
++------------+-----+
+",
+            r"+------------+-----+
+| Name       | Qty |
++------------+-----+
+| Cherry     | 100 |
++------------+-----+
+| Date       |   7 |
++------------+-----+
+",
+            r"+------------+-----+
+| Name       | Qty |
++------------+-----+
+| Elderberry |  42 |
++------------+-----+
+",
+        ];
+        for page in &pages {
+            println!("{}", page);
+        }
+        assert_eq!(expected, pages);
+    }
+
+    #[test]
+    fn inner_style() {
+        let mut builder = Table::builder()
+            .style(TableStyle::extended())
+            .inner_style(TableStyle::thin())
+            .to_owned();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let expected = r"╔═════════════════════════════════════════════════════════════════════════════════╗
+║                            This is some centered text                           ║
+╠────────────────────────────────────────┬────────────────────────────────────────╣
+║ This is left aligned text              │             This is right aligned text ║
+╠────────────────────────────────────────┼────────────────────────────────────────╣
+║ This is left aligned text              │             This is right aligned text ║
+╠────────────────────────────────────────┴────────────────────────────────────────╣
+║ This is some really really really really really really really really really tha ║
+║ t is going to wrap to the next line                                             ║
+╚═════════════════════════════════════════════════════════════════════════════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn row_padding() {
+        let table = Table::builder()
+            .style(TableStyle::simple())
+            .row_padding(1)
+            .rows(rows![
+                row![
+                    "A",
+                    TableCell::builder("B").vertical_alignment(VerticalAlignment::Bottom)
+                ],
+                row!["C", "D\nE"]
+            ])
+            .build();
+
+        let expected = r"+---+---+
+|   |   |
+| A | B |
+|   |   |
++---+---+
+|   |   |
+| C | D |
+|   | E |
+|   |   |
++---+---+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn left_and_right_borders() {
+        let render = |has_left_border: bool, has_right_border: bool| {
+            Table::builder()
+                .style(TableStyle::thin())
+                .has_left_border(has_left_border)
+                .has_right_border(has_right_border)
+                .rows(rows![
+                    row![TableCell::builder("ABC").col_span(2)],
+                    row!["A", "B"]
+                ])
+                .build()
+                .render()
+        };
+
+        let expected = r"┌───────┐
+│ ABC   │
+├───┬───┤
+│ A │ B │
+└───┴───┘
+";
+        println!("{}", render(true, true));
+        assert_eq!(expected, render(true, true));
+
+        let expected = r"───────┐
+ ABC   │
+───┬───┤
+ A │ B │
+───┴───┘
+";
+        println!("{}", render(false, true));
+        assert_eq!(expected, render(false, true));
+
+        let expected = r"┌───────
+│ ABC   
+├───┬───
+│ A │ B 
+└───┴───
+";
+        println!("{}", render(true, false));
+        assert_eq!(expected, render(true, false));
+
+        let expected = r"───────
+ ABC   
+───┬───
+ A │ B 
+───┴───
+";
+        println!("{}", render(false, false));
+        assert_eq!(expected, render(false, false));
+    }
+
+    #[test]
+    fn rendered_size() {
+        let mut builder = Table::builder();
+        add_data_to_test_table(&mut builder);
+        let mut table = builder.build();
+        assert_eq!((83, 10), table.rendered_size());
+
+        table.separate_rows = false;
+        table.has_left_border = false;
+        table.row_padding = 1;
+        let rendered = table.render();
+        let width = rendered.lines().map(string_width).max().unwrap();
+        assert_eq!((width, rendered.lines().count()), table.rendered_size());
+    }
+
+    #[test]
+    fn column_and_row_count() {
+        let mut table = Table::new();
+        assert_eq!(0, table.column_count());
+        assert_eq!(0, table.row_count());
+
+        table.add_row(Row::new(vec!["a", "b"]));
+        table.add_row(Row::new(vec![
+            TableCell::new("c"),
+            TableCell::builder("d").col_span(3).build(),
+        ]));
+        table.add_row(Row::new(vec!["e"]));
+        assert_eq!(2, table.column_count());
+        assert_eq!(3, table.row_count());
+    }
+
+    #[test]
+    fn render_into() {
+        let mut builder = Table::builder();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let mut buf = String::with_capacity(4096);
+        table.render_into(&mut buf);
+        assert_eq!(table.render(), buf);
+        let capacity = buf.capacity();
+
+        table.render_into(&mut buf);
+        assert_eq!(table.render(), buf);
+        assert_eq!(capacity, buf.capacity());
+    }
+
+    #[test]
+    fn freeze() {
+        let mut builder = Table::builder();
+        add_data_to_test_table(&mut builder);
+        let mut table = builder.build();
+        let expected = table.render();
+
+        table.freeze();
+        assert!(table.is_frozen());
+        for _ in 0..10 {
+            assert_eq!(expected, table.render());
+        }
+
+        table.add_row(Row::new(vec!["short"]));
+        assert!(!table.is_frozen());
+        table.remove_row(4);
+
+        table.freeze();
+        table.rows.truncate(3);
+        let frozen = table.render();
+        table.unfreeze();
+        assert_ne!(frozen, table.render());
+    }
+
+    #[test]
+    fn freeze_derived_tables() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec!["a", "b", "c"]));
+        table.add_row(Row::new(vec!["d", "e", "f"]));
+        let transposed = table.transpose().render();
+        let to_width = table.render_to_width(40);
+        let plain = table.render_plain();
+        let canonical = table.render_canonical();
+        table.hidden_columns.insert(0);
+        let hidden = table.render();
+        table.hidden_columns.clear();
+
+        table.freeze();
+        assert!(!table.transpose().is_frozen());
+        assert_eq!(transposed, table.transpose().render());
+        assert_eq!(to_width, table.render_to_width(40));
+        assert_eq!(plain, table.render_plain());
+        assert_eq!(canonical, table.render_canonical());
+        assert!(!table.with_max_rows(10).is_frozen());
+        table.hidden_columns.insert(0);
+        assert_eq!(hidden, table.render());
+    }
+
+    #[test]
+    fn build_owned() {
+        let mut builder = Table::builder();
+        builder.header(Row::new(vec!["Index", "Data"]));
+        builder.rows(
+            (0..1000)
+                .map(|i| Row::new(vec![i.to_string(), "data ".repeat(i % 50)]))
+                .collect(),
+        );
+        let expected = builder.build();
+        let table = builder.build_owned();
+
+        assert!(table.has_header);
+        assert_eq!(1001, table.rows.len());
+        for (row, expected_row) in table.rows.iter().zip(&expected.rows) {
+            for (cell, expected_cell) in row.cells.iter().zip(&expected_row.cells) {
+                assert_eq!(expected_cell.data, cell.data);
+            }
+        }
+    }
+
+    #[test]
+    fn option_cells() {
+        let none: Option<i32> = None;
+        let table = Table::builder()
+            .rows(rows![
+                row![none, Some(5)],
+                row![Some("Some text"), Option::<&str>::None]
+            ])
+            .none_placeholder("-")
+            .build();
+
+        let expected = r"╔═══════════╦═══╗
+║ -         ║ 5 ║
+╠═══════════╬═══╣
+║ Some text ║ - ║
+╚═══════════╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn append() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Value"])
+            .rows(rows![row!["a", 1]])
+            .build();
+        let other = Table::builder()
+            .rows(rows![row!["A longer name", 2], row!["b", 300]])
+            .build();
+        assert!(table.append(other).is_ok());
+
+        let expected = r"╔═══════════════╦═══════╗
+║ Name          ║ Value ║
+╠═══════════════╬═══════╣
+║ a             ║     1 ║
+╠═══════════════╬═══════╣
+║ A longer name ║     2 ║
+╠═══════════════╬═══════╣
+║ b             ║   300 ║
+╚═══════════════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let wider = Table::builder().rows(rows![row!["x", "y", "z"]]).build();
+        let returned = table.append(wider.clone()).unwrap_err();
+        assert_eq!(wider, returned);
+        assert_eq!(4, table.row_count());
+
+        let mut empty = Table::new();
+        assert!(empty.append(wider).is_ok());
+        assert_eq!(3, empty.column_count());
+    }
+
+    #[test]
+    fn beside() {
+        let left = Table::builder().rows(rows![row!["Short"]]).build();
+        let right = Table::builder()
+            .rows(rows![row!["A", "B"], row!["Taller", "table"]])
+            .build();
+        let table = left.beside(&right);
+
+        let expected = r"╔═══════╗  ╔════════╦═══════╗
+║ Short ║  ║ A      ║ B     ║
+╚═══════╝  ╠════════╬═══════╣
+           ║ Taller ║ table ║
+           ╚════════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render().replace('\0', ""));
+    }
+
+    #[test]
+    fn add_summary_row() {
+        let mut table = Table::builder()
+            .header(row!["Item", "Quantity"])
+            .rows(rows![
+                row!["Apples", 3],
+                row!["Pears", 12],
+                row!["Plums", 7]
+            ])
+            .build();
+        table.add_summary_row(|column, values| match column {
+            0 => Some("Total".to_string()),
+            _ => Some(
+                values
+                    .iter()
+                    .map(|value| value.parse::<i32>().unwrap())
+                    .sum::<i32>()
+                    .to_string(),
+            ),
+        });
+
+        let expected = r"╔════════╦══════════╗
+║ Item   ║ Quantity ║
+╠════════╬══════════╣
+║ Apples ║        3 ║
+╠════════╬══════════╣
+║ Pears  ║       12 ║
+╠════════╬══════════╣
+║ Plums  ║        7 ║
+╠════════╬══════════╣
+║ Total  ║       22 ║
+╚════════╩══════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn summary_row_separator() {
+        let mut table = Table::builder()
+            .style(TableStyle::thin())
             .separate_rows(false)
+            .header(row!["Item", "Quantity"])
+            .rows(rows![row!["Apples", 3], row!["Pears", 12]])
+            .build();
+        table.add_summary_row(|column, values| match column {
+            0 => Some("Total".to_string()),
+            _ => Some(values.len().to_string()),
+        });
+
+        let expected = r"┌────────┬──────────┐
+│ Item   │ Quantity │
+├────────┼──────────┤
+│ Apples │        3 │
+│ Pears  │       12 │
+┝━━━━━━━━┿━━━━━━━━━━┥
+│ Total  │        2 │
+└────────┴──────────┘
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+        assert_eq!((21, 8), table.rendered_size());
+
+        table.style = TableStyle::simple();
+        table.footer_separator_style = Some(TableStyle::heavy());
+        let expected = r"+--------+----------+
+| Item   | Quantity |
++--------+----------+
+| Apples |        3 |
+| Pears  |       12 |
+┣━━━━━━━━╋━━━━━━━━━━┫
+| Total  |        2 |
++--------+----------+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn center_bias() {
+        let table = Table::builder()
+            .rows(rows![
+                row!["abc"],
+                row![TableCell::builder("ab")
+                    .alignment(Alignment::Center)
+                    .center_bias(CenterBias::Left)],
+                row![TableCell::builder("ab")
+                    .alignment(Alignment::Center)
+                    .center_bias(CenterBias::Right)]
+            ])
+            .build();
+
+        let expected = r"╔═════╗
+║ abc ║
+╠═════╣
+║  ab ║
+╠═════╣
+║ ab  ║
+╚═════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn from_markdown() {
+        let markdown = r"| Name            | Quantity | Price |
+| :-------------- | :------: | ----: |
+| Apple           | 3        | 1.50  |
+| Banana \| Split | 12       |       |
+";
+        let table = Table::from_markdown(markdown).unwrap();
+        assert!(table.has_header);
+        assert_eq!("Banana | Split", table.rows[2].cells[0].data);
+        assert_eq!(markdown, table.render_markdown());
+
+        let table = Table::from_markdown("Name|Price\n-|-:\nApple|1.50|extra\nPear").unwrap();
+        let expected = r"╔═══════╦═══════╗
+║ Name  ║ Price ║
+╠═══════╬═══════╣
+║ Apple ║  1.50 ║
+╠═══════╬═══════╣
+║ Pear  ║       ║
+╚═══════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        assert_eq!(
+            ParseError::MissingHeader,
+            Table::from_markdown("").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::MissingDelimiterRow,
+            Table::from_markdown("| a | b |").unwrap_err()
+        );
+        assert_eq!(
+            ParseError::InvalidDelimiter(1),
+            Table::from_markdown("| a | b |\n| --- | -x- |").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn render_streaming() {
+        let table = Table::builder()
+            .header(row!["Line", "Message"])
+            .fixed_column_width(0, 6)
+            .fixed_column_width(1, 14)
+            .build();
+        let rows = (0..1000).map(|i| row![i, format!("message {}", i)]);
+
+        let mut buf = Vec::new();
+        table.render_streaming(rows, &mut buf).unwrap();
+        let rendered = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(2003, lines.len());
+        assert_eq!("╔══════╦══════════════╗", lines[0]);
+        assert_eq!("║ Line ║ Message      ║", lines[1]);
+        assert_eq!("╠══════╬══════════════╣", lines[2]);
+        assert_eq!("║    0 ║ message 0    ║", lines[3]);
+        assert_eq!("║  999 ║ message 999  ║", lines[2001]);
+        assert_eq!("╚══════╩══════════════╝", lines[2002]);
+        assert!(lines.iter().all(|line| string_width(line) == 23));
+    }
+
+    #[test]
+    fn render_streaming_and_column_pages_hide_columns() {
+        let mut table = Table::builder()
             .style(TableStyle::simple())
+            .header(row!["Name", "Secret", "City"])
+            .rows(rows![row!["Alice", "secret", "Paris"]])
+            .build();
+        table.hidden_columns.insert(1);
+        table.skip_empty_rows = true;
+
+        let rows = vec![row!["Bob", "secret", "Lyon"], row!["", "", ""]];
+        let mut buf = Vec::new();
+        table.render_streaming(rows, &mut buf).unwrap();
+        let expected = r"+-------+-------+
+| Name  | City  |
++-------+-------+
+| Alice | Paris |
++-------+-------+
+| Bob   | Lyon  |
++-------+-------+
+";
+        println!("{}", String::from_utf8_lossy(&buf));
+        assert_eq!(expected, String::from_utf8(buf).unwrap());
+
+        let pages = table.render_column_pages_with_key(12, Some(2));
+        println!("{}", pages.join("\n"));
+        assert_eq!(1, pages.len());
+        assert!(pages[0].contains("| City  | Name  |"));
+
+        table.rtl = true;
+        let mut buf = Vec::new();
+        table
+            .render_streaming(vec![row!["Bob", "secret", "Lyon"]], &mut buf)
+            .unwrap();
+        let rendered = String::from_utf8(buf).unwrap();
+        println!("{}", rendered);
+        assert!(rendered.contains("|  Lyon |   Bob |"));
+        assert!(!rendered.contains("secret"));
+        let pages = table.render_column_pages_with_key(12, Some(2));
+        assert!(pages.iter().all(|page| page.contains("City")));
+    }
+
+    #[test]
+    fn render_column_pages() {
+        let table = Table::builder()
+            .header(row!["Id", "Name", "Age", "City", "Country", "Email"])
             .rows(rows![
+                row![1, "Alice", 30, "Paris", "France", "alice@example.com"],
                 row![
-                    TableCell::builder("A").alignment(Alignment::Center),
-                    TableCell::builder("B").alignment(Alignment::Center),
-                ],
-                row![TableCell::builder("1"), TableCell::builder(1),],
-                row![TableCell::builder("2"), TableCell::builder(10),],
-                row![TableCell::builder("3"), TableCell::builder(100),],
+                    2,
+                    "Bob",
+                    25,
+                    TableCell::builder("Berlin, Germany").col_span(2),
+                    "bob@example.com"
+                ]
             ])
             .build();
 
-        let expected = r"+---+-----+
-| A |  B  |
-| 1 | 1   |
-| 2 | 10  |
-| 3 | 100 |
-+---+-----+
+        let pages = table.render_column_pages(36);
+        assert_eq!(2, pages.len());
+        let expected = r"╔════╦═══════╦═════╦════════╗
+║ Id ║ Name  ║ Age ║ City   ║
+╠════╬═══════╬═════╬════════╣
+║  1 ║ Alice ║  30 ║ Paris  ║
+╠════╬═══════╬═════╬════════╣
+║  2 ║ Bob   ║  25 ║ Berlin ║
+║    ║       ║     ║ , Germ ║
+║    ║       ║     ║ any    ║
+╚════╩═══════╩═════╩════════╝
+";
+        println!("{}", pages[0]);
+        assert_eq!(expected, pages[0]);
+
+        let pages = table.render_column_pages_with_key(36, Some(0));
+        assert_eq!(2, pages.len());
+        let expected = r"╔════╦═════════╦═══════════════════╗
+║ Id ║ Country ║ Email             ║
+╠════╬═════════╬═══════════════════╣
+║  1 ║ France  ║ alice@example.com ║
+╠════╬═════════╬═══════════════════╣
+║  2 ║ Berlin, ║ bob@example.com   ║
+║    ║  German ║                   ║
+║    ║ y       ║                   ║
+╚════╩═════════╩═══════════════════╝
+";
+        println!("{}", pages[1]);
+        assert_eq!(expected, pages[1]);
+    }
+
+    #[test]
+    fn row_alignment_and_style() {
+        let table = Table::builder()
+            .column_alignment(1, Alignment::Center)
+            .rows(rows![
+                row!["Extended", "style", "row"],
+                row![
+                    "Thin",
+                    "st",
+                    TableCell::builder("row").alignment(Alignment::Left)
+                ]
+                .with_style(TableStyle::thin())
+                .with_alignment(Alignment::Right),
+                row!["Extended", "style", "row"]
+            ])
+            .build();
+
+        let expected = r"╔══════════╦═══════╦═════╗
+║ Extended ║ style ║ row ║
+├──────────┼───────┼─────┤
+│     Thin │    st │ row │
+╠══════════╬═══════╬═════╣
+║ Extended ║ style ║ row ║
+╚══════════╩═══════╩═════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn with_max_rows() {
+        let mut table = Table::builder().header(row!["Number", "Square"]).build();
+        for i in 1..=10 {
+            table.add_row(row![i, i * i]);
+        }
+        let preview = table.with_max_rows(3);
+        assert_eq!(5, preview.row_count());
+
+        let expected = r"╔════════╦════════╗
+║ Number ║ Square ║
+╠════════╬════════╣
+║      1 ║      1 ║
+╠════════╬════════╣
+║      2 ║      4 ║
+╠════════╬════════╣
+║      3 ║      9 ║
+╠════════╩════════╣
+║  … 7 more rows  ║
+╚═════════════════╝
+";
+        println!("{}", preview.render());
+        assert_eq!(expected, preview.render());
+        assert_eq!(table.render(), table.with_max_rows(10).render());
+    }
+
+    #[test]
+    fn line_ending() {
+        let table = Table::builder()
+            .rows(rows![row!["a", "multiple\nlines"], row!["b", "c"]])
+            .line_ending(LineEnding::CrLf)
+            .build();
+
+        let expected = "╔═══╦══════════╗\r\n║ a ║ multiple ║\r\n║   ║ lines    ║\r\n╠═══╬══════════╣\r\n║ b ║ c        ║\r\n╚═══╩══════════╝\r\n";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn to_lines() {
+        let mut builder = Table::builder();
+        add_data_to_test_table(&mut builder);
+        let table = builder.build();
+
+        let lines = table.to_lines();
+        assert_eq!(10, lines.len());
+        assert_eq!(
+            "║ t is going to wrap to the next line                                             ║",
+            lines[8]
+        );
+        assert_eq!(table.render(), lines.join("\n") + "\n");
+    }
+
+    #[test]
+    fn to_lines_crlf() {
+        let table = Table::builder()
+            .line_ending(LineEnding::CrLf)
+            .title("Title")
+            .rows(rows![row!["a\nb", "c"]])
+            .build();
+
+        let lines = table.to_lines();
+        assert_eq!(5, lines.len());
+        assert_eq!("Title", lines[0].trim());
+        assert!(lines.iter().all(|line| !line.contains('\r')));
+        assert_eq!(table.render(), lines.join("\r\n") + "\r\n");
+    }
+
+    #[test]
+    fn ambiguous_wide() {
+        let mut builder = Table::builder()
+            .style(TableStyle::simple())
+            .rows(rows![row!["§1", "a"], row!["§§", "b"]])
+            .to_owned();
+
+        let expected = r"+----+---+
+| §1 | a |
++----+---+
+| §§ | b |
++----+---+
+";
+        let table = builder.build();
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let expected = r"+------+---+
+| §1  | a |
++------+---+
+| §§ | b |
++------+---+
+";
+        let table = builder.ambiguous_wide(true).build();
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn hidden_columns() {
+        let table = Table::builder()
+            .rows(rows![
+                row!["Left", "Middle", "Right"],
+                row![TableCell::builder("Spans all three columns").col_span(3)],
+                row![TableCell::builder("Spans two").col_span(2), "Last"]
+            ])
+            .hide_column(1)
+            .build();
+
+        let expected = r"╔════════════╦═════════════╗
+║ Left       ║ Right       ║
+╠════════════╩═════════════╣
+║ Spans all three columns  ║
+╠════════════╦═════════════╣
+║ Spans two  ║ Last        ║
+╚════════════╩═════════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn merge_fn() {
+        // Line separators end above a spanning cell with '┴' and start below it with '┬'
+        fn merge(top: char, bottom: char, _pos: RowPosition) -> char {
+            match (top, bottom) {
+                (_, '═') => '┴',
+                ('┴', _) => '┬',
+                _ => '╬',
+            }
+        }
+
+        let mut table = Table::new();
+        table.style = TableStyle::builder().merge_fn(merge).build();
+        table.add_row(row!["a", "b", "c"]);
+        table.add_row(row![TableCell::builder("spans").col_span(2), "d"]);
+        table.add_row(row!["e", "f", "g"]);
+
+        let expected = r"╔═══╦════╦═══╗
+║ a ║ b  ║ c ║
+╠═══┴════╬═══╣
+║ spans  ║ d ║
+╠═══┬════╬═══╣
+║ e ║ f  ║ g ║
+╚═══╩════╩═══╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn grid() {
+        let table = Table::builder()
+            .rows(rows![
+                row![TableCell::builder("spans").col_span(2), "a"],
+                row![TableCell::builder("tall").row_span(2), "b", "c"],
+                row!["d"],
+                row!["e"]
+            ])
+            .build();
+
+        let grid = table.grid();
+        let data: Vec<Vec<Option<&str>>> = grid
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.map(|cell| cell.data.as_str()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![
+                vec![Some("spans"), None, Some("a")],
+                vec![Some("tall"), Some("b"), Some("c")],
+                vec![None, Some("d"), None],
+                vec![Some("e"), None, None],
+            ],
+            data
+        );
+        assert_eq!(2, grid[0][0].unwrap().col_span);
+    }
+
+    #[test]
+    fn strip_ansi() {
+        let table = Table::builder()
+            .rows(rows![row![
+                "\u{1b}[31mred\u{1b}[0m",
+                TableCell::builder("green").fg(Color::Green)
+            ]])
+            .strip_ansi(true)
+            .build();
+
+        let expected = r"╔═════╦═══════╗
+║ red ║ green ║
+╚═════╩═══════╝
+";
+        println!("{}", table.render());
+        assert!(!table.render().contains('\u{1b}'));
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn wrap_width_of_padding() {
+        let cell = TableCell::new("abc");
+        assert_eq!(vec![" a ", " b ", " c "], cell.wrapped_content(2));
+        assert_eq!(vec![" a ", " b ", " c "], cell.wrapped_content(0));
+    }
+
+    #[test]
+    fn skip_empty_rows() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Value"])
+            .rows(rows![row!["a", 1], row!["", " "], row!["b", 2]])
+            .build();
+        assert!(!table.rows[0].is_empty());
+        assert!(table.rows[2].is_empty());
+
+        let expected = r"╔══════╦═══════╗
+║ Name ║ Value ║
+╠══════╬═══════╣
+║ a    ║     1 ║
+╠══════╬═══════╣
+║      ║       ║
+╠══════╬═══════╣
+║ b    ║     2 ║
+╚══════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        table.skip_empty_rows = true;
+        let expected = r"╔══════╦═══════╗
+║ Name ║ Value ║
+╠══════╬═══════╣
+║ a    ║     1 ║
+╠══════╬═══════╣
+║ b    ║     2 ║
+╚══════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn from_columns() {
+        let table = Table::from_columns(vec![
+            vec!["Name", "Apple", "Pear"],
+            vec!["Colour", "Red"],
+            vec!["Stock", "3", "12", "7"],
+        ]);
+
+        let expected = r"╔═══════╦════════╦═══════╗
+║ Name  ║ Colour ║ Stock ║
+╠═══════╬════════╬═══════╣
+║ Apple ║ Red    ║ 3     ║
+╠═══════╬════════╬═══════╣
+║ Pear  ║        ║ 12    ║
+╠═══════╬════════╬═══════╣
+║       ║        ║ 7     ║
+╚═══════╩════════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn decimal_alignment() {
+        let table = Table::builder()
+            .header(row!["Item", "Price"])
+            .rows(rows![
+                row!["Coffee", "1.5"],
+                row!["Lunch", "12.25"],
+                row!["Rent", "100.0"],
+                row!["Tip", "5"]
+            ])
+            .column_alignment(1, Alignment::Decimal)
+            .build();
+
+        let expected = r"╔════════╦════════╗
+║ Item   ║  Price ║
+╠════════╬════════╣
+║ Coffee ║   1.5  ║
+╠════════╬════════╣
+║ Lunch  ║  12.25 ║
+╠════════╬════════╣
+║ Rent   ║ 100.0  ║
+╠════════╬════════╣
+║ Tip    ║   5    ║
+╚════════╩════════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn cell_and_row_height() {
+        let wrapping = TableCell::new("abcdefghi");
+        // Each line holds three characters between the padding on both sides
+        assert_eq!(3, wrapping.height(5));
+        assert_eq!(1, wrapping.height(11));
+
+        let new_lines = TableCell::new("one\ntwo\nthree");
+        assert_eq!(3, new_lines.height(usize::MAX));
+
+        let row = row![wrapping, new_lines, "short"];
+        assert_eq!(3, row.height(&[5, 7, 7]));
+        assert_eq!(4, row.height(&[5, 5, 7]));
+    }
+
+    #[test]
+    fn truncation() {
+        let text = "abcdefghijklmnopqrst";
+        let cell = |truncation: Truncation| {
+            TableCell::builder(text)
+                .wrap_mode(WrapMode::Truncate)
+                .truncation(truncation)
+                .pad_content(false)
+                .build()
+        };
+        assert_eq!(
+            vec!["\0abcdefg…\0"],
+            cell(Truncation::End).wrapped_content(8)
+        );
+        assert_eq!(
+            vec!["\0…nopqrst\0"],
+            cell(Truncation::Start).wrapped_content(8)
+        );
+        assert_eq!(
+            vec!["\0abcd…rst\0"],
+            cell(Truncation::Middle).wrapped_content(8)
+        );
+
+        let ascii = TableCell::builder(text)
+            .wrap_mode(WrapMode::Truncate)
+            .ellipsis("...")
+            .pad_content(false)
+            .build();
+        assert_eq!(vec!["\0abcde...\0"], ascii.wrapped_content(8));
+
+        let table = Table::builder()
+            .rows(rows![row![
+                TableCell::builder(text).wrap_mode(WrapMode::Truncate)
+            ]])
+            .max_column_width(10)
+            .build();
+        assert_eq!(" abcde...\n", table.render_plain());
+    }
+
+    #[test]
+    fn title_and_caption() {
+        let table = Table::builder()
+            .rows(rows![row!["Apple", 3], row!["Pear", 12]])
+            .title("Fruit in stock at the shop")
+            .caption("Counted today")
+            .build();
+
+        let expected = r"Fruit in stock
+  at the shop 
+╔═══════╦════╗
+║ Apple ║  3 ║
+╠═══════╬════╣
+║ Pear  ║ 12 ║
+╚═══════╩════╝
+ Counted today
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!((14, 8), table.rendered_size());
     }
 
     #[test]
-    fn uneven_center_alignment() {
+    fn display_row() {
+        let row = row!["Apple", 3, "Red"];
+        let expected = r"╔═══════╦═══╦═════╗
+║ Apple ║ 3 ║ Red ║
+╚═══════╩═══╩═════╝
+";
+        println!("{}", row);
+        assert_eq!(expected, row.to_string());
+    }
+
+    #[test]
+    fn table_equality() {
+        let build = || {
+            Table::builder()
+                .style(TableStyle::thin())
+                .header(row!["Name", "Value"])
+                .rows(rows![row![
+                    "a",
+                    TableCell::builder(1).alignment(Alignment::Center)
+                ]])
+                .build()
+        };
+        let table = build();
+        assert_eq!(table, build());
+        assert_eq!(
+            table.rows[1],
+            row!["a", TableCell::builder(1).alignment(Alignment::Center)]
+        );
+
+        let mut other = build();
+        other.rows[1].cells[1].col_span = 2;
+        assert_ne!(table, other);
+
+        let mut other = build();
+        other.style = TableStyle::rounded();
+        assert_ne!(table, other);
+
+        let mut frozen = build();
+        frozen.freeze();
+        assert_eq!(table, frozen);
+
+        fn merge(_: char, _: char, _: RowPosition) -> char {
+            '*'
+        }
+        let merged = TableStyle::builder()
+            .based_on(TableStyle::thin())
+            .merge_fn(merge)
+            .build();
+        assert_eq!(TableStyle::thin(), merged);
+    }
+
+    #[test]
+    fn style_names() {
+        for name in &[
+            "simple", "extended", "thin", "heavy", "rounded", "elegant", "blank", "empty",
+            "markdown", "psql",
+        ] {
+            let style = TableStyle::from_name(name).unwrap();
+            assert_eq!(Some(*name), style.name());
+        }
+        assert_eq!(Some("extended"), TableStyle::double().name());
+        assert_eq!(Some("simple"), TableStyle::ascii().name());
+        assert_eq!(None, TableStyle::from_name("fancy"));
+
+        let custom = TableStyle::builder().horizontal('~').build();
+        assert_eq!(None, custom.name());
+    }
+
+    #[test]
+    fn header_separator_style() {
         let table = Table::builder()
-            .separate_rows(false)
-            .style(TableStyle::simple())
+            .style(TableStyle::thin())
+            .header_separator_style(TableStyle::extended())
+            .header(row!["Name", "Value"])
             .rows(rows![
-                row![TableCell::builder("A").alignment(Alignment::Center),],
-                row![TableCell::builder(11),],
-                row![TableCell::builder(2),],
-                row![TableCell::builder(3),],
+                row!["a", "1"],
+                row![TableCell::builder("spans").col_span(2)]
             ])
             .build();
 
-        let expected = r"+-----+
-|  A  |
-| 11  |
-| 2   |
-| 3   |
-+-----+
+        let expected = r"┌──────┬───────┐
+│ Name │ Value │
+╠══════╬═══════╣
+│ a    │ 1     │
+├──────┴───────┤
+│ spans        │
+└──────────────┘
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
     }
 
     #[test]
-    fn uneven_center_alignment_2() {
-        let table = Table::builder()
-            .separate_rows(false)
-            .style(TableStyle::simple())
-            .rows(rows![row![
-                TableCell::builder("A1").alignment(Alignment::Center),
-                TableCell::builder("B").alignment(Alignment::Center),
-            ],])
+    fn reverse_rows() {
+        let mut table = Table::builder()
+            .header(row!["Number"])
+            .rows(rows![row![1], row![2], row![3]])
             .build();
-        println!("{}", table.render());
-        let expected = r"+----+---+
-| A1 | B |
-+----+---+
-";
-        println!("{}", table.render());
-        assert_eq!(expected, table.render());
+        table.reverse_rows();
+        assert_eq!(vec![row!["Number"], row![3], row![2], row![1]], table.rows);
     }
 
     #[test]
-    fn simple_table_style() {
-        let mut builder = TableBuilder::new().style(TableStyle::simple()).to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn dedup_rows() {
+        let mut table = Table::builder()
+            .header(row!["Level"])
+            .rows(rows![
+                row!["Level"],
+                row!["info"],
+                row!["info"],
+                row!["warn"],
+                row!["info"]
+            ])
+            .build();
+        table.dedup_rows();
+        assert_eq!(
+            vec![
+                row!["Level"],
+                row!["Level"],
+                row!["info"],
+                row!["warn"],
+                row!["info"]
+            ],
+            table.rows
+        );
+    }
 
-        let expected = r"+---------------------------------------------------------------------------------+
-|                            This is some centered text                           |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is some really really really really really really really really really tha |
-| t is going to wrap to the next line                                             |
-+---------------------------------------------------------------------------------+
+    #[test]
+    fn with_index_column() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Colour"])
+            .rows(rows![
+                row!["Apple", "Red"],
+                row![TableCell::builder("Pear").col_span(2)],
+                row!["Plum", "Purple"]
+            ])
+            .column_alignment(1, Alignment::Right)
+            .build();
+        table.with_index_column("#");
+
+        let expected = r"╔═══╦═══════╦════════╗
+║ # ║ Name  ║ Colour ║
+╠═══╬═══════╬════════╣
+║ 1 ║ Apple ║    Red ║
+╠═══╬═══════╩════════╣
+║ 2 ║ Pear           ║
+╠═══╬═══════╦════════╣
+║ 3 ║ Plum  ║ Purple ║
+╚═══╩═══════╩════════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
     }
 
     #[test]
-    fn uneven_with_varying_col_span() {
-        let table = Table::builder()
-            .separate_rows(true)
-            .style(TableStyle::simple())
+    fn format_cells() {
+        let mut table = Table::builder()
+            .header(row!["Account", "Change"])
             .rows(rows![
-                row![
-                    TableCell::builder("A1111111").alignment(Alignment::Center),
-                    TableCell::builder("B").alignment(Alignment::Center),
-                ],
-                row![1, "1"],
-                row![2, "10"],
-                row![
-                    TableCell::builder(3)
-                        .alignment(Alignment::Left)
-                        .pad_content(false),
-                    "100",
-                ],
-                row![TableCell::builder("S")
-                    .alignment(Alignment::Center)
-                    .col_span(2),],
+                row!["Savings", "10.5"],
+                row!["Checking", "-20"],
+                row![TableCell::builder("-1").col_span(2)]
             ])
             .build();
+        table.format_cells(|row, column, data| match data.parse::<f64>() {
+            Ok(value) if row > 0 && column == 1 && value < 0.0 => {
+                Some(TableCell::builder(data).fg(Color::Red).build())
+            }
+            _ => None,
+        });
 
-        let expected = "+----------+-----+
-| A1111111 |  B  |
-+----------+-----+
-| 1        | 1   |
-+----------+-----+
-| 2        | 10  |
-+----------+-----+
-|\03\0         | 100 |
-+----------+-----+
-|        S       |
-+----------------+
+        assert_eq!(Some(Color::Red), table.rows[2].cells[1].fg);
+        assert_eq!(None, table.rows[1].cells[1].fg);
+        assert_eq!(None, table.rows[3].cells[0].fg);
+        assert!(table.render().contains("\u{1b}[31m -20    \u{1b}[0m"));
+    }
+
+    #[test]
+    fn borders() {
+        let build = |borders: Borders| {
+            Table::builder()
+                .rows(rows![row!["a", "b"], row!["c", "d"]])
+                .borders(borders)
+                .build()
+        };
+
+        let table = build(Borders::ALL);
+        assert_eq!(Borders::ALL, table.borders());
+        let expected = r"╔═══╦═══╗
+║ a ║ b ║
+╠═══╬═══╣
+║ c ║ d ║
+╚═══╩═══╝
 ";
         println!("{}", table.render());
-        assert_eq!(expected.trim(), table.render().trim());
+        assert_eq!(expected, table.render());
+
+        let table = build(Borders::OUTER);
+        assert_eq!(Borders::OUTER, table.borders());
+        let expected = r"╔═══════╗
+║ a   b ║
+║ c   d ║
+╚═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let table = build(Borders::NONE);
+        assert_eq!(Borders::NONE, table.borders());
+        let expected = " a   b \n c   d \n";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        let table = build(Borders::TOP | Borders::INNER_VERTICAL);
+        assert!(table.borders().contains(Borders::TOP));
+        assert!(!table.borders().contains(Borders::OUTER));
     }
 
-    // TODO - The output of this test isn't ideal. There is probably a better way to calculate the
-    // the column/row layout that would improve this
     #[test]
-    fn uneven_with_varying_col_span_2() {
+    fn column_separator() {
+        let style = TableStyle::builder().column_separator('│').build();
         let table = Table::builder()
-            .separate_rows(false)
-            .style(TableStyle::simple())
+            .style(style)
             .rows(rows![
-                row![
-                    TableCell::builder("A").alignment(Alignment::Center),
-                    TableCell::builder("B").alignment(Alignment::Center),
-                ],
-                row![TableCell::builder(1), TableCell::builder(1),],
-                row![TableCell::builder(2), TableCell::builder(10),],
-                row![TableCell::builder(3), TableCell::builder(100),],
-                row![TableCell::builder("Spanner")
-                    .col_span(2)
-                    .alignment(Alignment::Center),],
+                row!["a", "b", "c"],
+                row![TableCell::builder("spans").col_span(2), "d"]
             ])
             .build();
 
-        let expected = "+------+-----+
-|   A  |  B  |
-| 1    | 1   |
-| 2    | 10  |
-| 3    | 100 |
-|   Spanner  |
-+------------+
+        let expected = r"╔═══╦════╦═══╗
+║ a │ b  │ c ║
+╠═══╩════╬═══╣
+║ spans  │ d ║
+╚════════╩═══╝
 ";
         println!("{}", table.render());
-        assert_eq!(expected.trim(), table.render().trim());
+        assert_eq!(expected, table.render());
     }
 
     #[test]
-    fn extended_table_style_wrapped() {
-        let table = Table::builder()
-            .max_column_width(40)
-            .style(TableStyle::extended())
-            .max_column_widths(vec![(0, 1), (1, 1)].into_iter().collect())
-            .rows(rows![
-                row![
-                    TableCell::builder("This is some centered text").alignment(Alignment::Center).col_span(2),
-                ],
-                row![
-                    TableCell::builder("This is left aligned text"),
-                    TableCell::builder("This is right aligned text").alignment(Alignment::Right),
-                ],
-                row![
-                    TableCell::builder("This is left aligned text"),
-                    TableCell::builder("This is right aligned text").alignment(Alignment::Right),
-                ],
-                row![
-                    TableCell::builder("This is some really really really really really really really really really that is going to wrap to the next line\n1\n2").col_span(2),
-                ],
-            ])
+    fn control_chars() {
+        let mut table = Table::builder()
+            .rows(rows![row!["a\rb", "\u{1b}[31mred\u{1b}[0m"]])
+            .control_chars(ControlChars::Replace)
             .build();
 
-        let expected = r"╔═══════╗
-║ This  ║
-║ is so ║
-║ me ce ║
-║ ntere ║
-║ d tex ║
-║   t   ║
-╠═══╦═══╣
-║ T ║ T ║
-║ h ║ h ║
-║ i ║ i ║
-║ s ║ s ║
-║   ║   ║
-║ i ║ i ║
-║ s ║ s ║
-║   ║   ║
-║ l ║ r ║
-║ e ║ i ║
-║ f ║ g ║
-║ t ║ h ║
-║   ║ t ║
-║ a ║   ║
-║ l ║ a ║
-║ i ║ l ║
-║ g ║ i ║
-║ n ║ g ║
-║ e ║ n ║
-║ d ║ e ║
-║   ║ d ║
-║ t ║   ║
-║ e ║ t ║
-║ x ║ e ║
-║ t ║ x ║
-║   ║ t ║
-╠═══╬═══╣
-║ T ║ T ║
-║ h ║ h ║
-║ i ║ i ║
-║ s ║ s ║
-║   ║   ║
-║ i ║ i ║
-║ s ║ s ║
-║   ║   ║
-║ l ║ r ║
-║ e ║ i ║
-║ f ║ g ║
-║ t ║ h ║
-║   ║ t ║
-║ a ║   ║
-║ l ║ a ║
-║ i ║ l ║
-║ g ║ i ║
-║ n ║ g ║
-║ e ║ n ║
-║ d ║ e ║
-║   ║ d ║
-║ t ║   ║
-║ e ║ t ║
-║ x ║ e ║
-║ t ║ x ║
-║   ║ t ║
-╠═══╩═══╣
-║ This  ║
-║ is so ║
-║ me re ║
-║ ally  ║
-║ reall ║
-║ y rea ║
-║ lly r ║
-║ eally ║
-║  real ║
-║ ly re ║
-║ ally  ║
-║ reall ║
-║ y rea ║
-║ lly r ║
-║ eally ║
-║  that ║
-║  is g ║
-║ oing  ║
-║ to wr ║
-║ ap to ║
-║  the  ║
-║ next  ║
-║ line  ║
-║ 1     ║
-║ 2     ║
-╚═══════╝
+        let expected = "╔═════╦═════╗\n║ a·b ║ \u{1b}[31mred\u{1b}[0m ║\n╚═════╩═════╝\n";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        table.control_chars = ControlChars::Remove;
+        let expected = "╔════╦═════╗\n║ ab ║ \u{1b}[31mred\u{1b}[0m ║\n╚════╩═════╝\n";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+    }
+
+    #[test]
+    fn hyphenate() {
+        let cell = |data: &str| {
+            TableCell::builder(data)
+                .wrap_style(WrapStyle::Word)
+                .hyphenate(true)
+                .build()
+        };
+        assert_eq!(
+            vec![" extraor- ", " dinaril- ", " y "],
+            cell("extraordinarily").wrapped_content(10)
+        );
+        assert_eq!(
+            vec![" a well- ", " known- ", " fact "],
+            cell("a well-known-fact").wrapped_content(9)
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let old = Table::from([["a", "b"], ["c", "d"]]);
+        let new = Table::from([["a", "b"], ["c", "e"]]);
+        let diff = new.diff(&old);
+
+        let highlighted: Vec<(usize, usize)> = diff
+            .rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| {
+                row.cells
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| cell.fg.is_some())
+                    .map(move |(j, _)| (i, j))
+            })
+            .collect();
+        assert_eq!(vec![(1, 2)], highlighted);
+        assert_eq!(Some(Color::Yellow), diff.rows[1].cells[2].fg);
+
+        let expected = "╔══╦═══╦═══╗
+║  ║ a ║ b ║
+╠══╬═══╬═══╣
+║  ║ c ║\u{1b}[33m e \u{1b}[0m║
+╚══╩═══╩═══╝
+";
+        println!("{}", diff.render());
+        assert_eq!(expected, diff.render());
+
+        let added = Table::from(vec![vec!["a", "b"], vec!["c", "d"], vec!["f"]]).diff(&old);
+        assert_eq!("+", added.rows[2].cells[0].data);
+        assert_eq!(Some(Color::Green), added.rows[2].cells[1].fg);
+
+        let removed = Table::from([["a", "b"]]).diff(&old);
+        assert_eq!("-", removed.rows[1].cells[0].data);
+        assert_eq!(Some(Color::Red), removed.rows[1].cells[2].fg);
+    }
+
+    #[test]
+    fn cell_from_string() {
+        let data = String::from("owned");
+        let ptr = data.as_ptr();
+        let cell = TableCell::from(data);
+        assert_eq!(ptr, cell.data.as_ptr());
+        assert_eq!(TableCell::new("owned"), cell);
+        assert_eq!(
+            TableCell::new("owned"),
+            TableCell::from_string("owned".to_string())
+        );
+    }
+
+    #[test]
+    fn cell_tag() {
+        let table = Table::builder()
+            .header(row!["Name", "Id"])
+            .rows(rows![row![
+                TableCell::builder("Alice").tag("user-1"),
+                TableCell::builder(1).tag("user-1")
+            ]])
+            .build();
+
+        let tags: Vec<Vec<Option<&str>>> = table
+            .grid()
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| cell.and_then(|cell| cell.tag.as_deref()))
+                    .collect()
+            })
+            .collect();
+        assert_eq!(
+            vec![vec![None, None], vec![Some("user-1"), Some("user-1")]],
+            tags
+        );
+        assert_eq!(None, TableCell::new("untagged").tag);
+    }
+
+    #[test]
+    fn pad_to_width() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Value"])
+            .rows(rows![row!["a", "1"]])
+            .build();
+        table.pad_to_width(80);
+        assert_eq!(vec![6, 71], table.column_widths());
+        assert_eq!(80, table.rendered_size().0);
+
+        let expected = r"╔══════╦═══════════════════════════════════════════════════════════════════════╗
+║ Name ║ Value                                                                 ║
+╠══════╬═══════════════════════════════════════════════════════════════════════╣
+║ a    ║ 1                                                                     ║
+╚══════╩═══════════════════════════════════════════════════════════════════════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+
+        table.padded_column = Some(0);
+        assert_eq!(vec![70, 7], table.column_widths());
+
+        table.pad_to_width(10);
+        assert_eq!(vec![6, 7], table.column_widths());
     }
 
     #[test]
-    fn elegant_table_style() {
-        let mut builder = Table::builder().style(TableStyle::elegant()).to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn section_break() {
+        let table = Table::builder()
+            .separate_rows(false)
+            .header(row!["Name", "Team"])
+            .rows(rows![
+                row!["Alice", "Red"],
+                row!["Bob", "Red"],
+                Row::section_break(),
+                row!["Carol", "Blue"]
+            ])
+            .build();
 
-        let expected = r"╔─────────────────────────────────────────────────────────────────────────────────╗
-│                            This is some centered text                           │
-╠────────────────────────────────────────╦────────────────────────────────────────╣
-│ This is left aligned text              │             This is right aligned text │
-╠────────────────────────────────────────┼────────────────────────────────────────╣
-│ This is left aligned text              │             This is right aligned text │
-╠────────────────────────────────────────╩────────────────────────────────────────╣
-│ This is some really really really really really really really really really tha │
-│ t is going to wrap to the next line                                             │
-╚─────────────────────────────────────────────────────────────────────────────────╝
+        let expected = r"╔═══════╦══════╗
+║ Name  ║ Team ║
+╠═══════╬══════╣
+║ Alice ║ Red  ║
+║ Bob   ║ Red  ║
+╚═══════╩══════╝
+                
+╔═══════╦══════╗
+║ Carol ║ Blue ║
+╚═══════╩══════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!((16, 10), table.rendered_size());
     }
 
     #[test]
-    fn thin_table_style() {
-        let mut builder = Table::builder().style(TableStyle::thin()).to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn section_break_with_row_separators() {
+        let table = Table::builder()
+            .rows(rows![
+                row!["Alice", "Red"],
+                row!["Bob", "Red"],
+                Row::section_break(),
+                row!["Carol", "Blue"],
+                row!["Dave", "Blue"]
+            ])
+            .build();
 
-        let expected = r"┌─────────────────────────────────────────────────────────────────────────────────┐
-│                            This is some centered text                           │
-├────────────────────────────────────────┬────────────────────────────────────────┤
-│ This is left aligned text              │             This is right aligned text │
-├────────────────────────────────────────┼────────────────────────────────────────┤
-│ This is left aligned text              │             This is right aligned text │
-├────────────────────────────────────────┴────────────────────────────────────────┤
-│ This is some really really really really really really really really really tha │
-│ t is going to wrap to the next line                                             │
-└─────────────────────────────────────────────────────────────────────────────────┘
+        let expected = r"╔═══════╦══════╗
+║ Alice ║ Red  ║
+╠═══════╬══════╣
+║ Bob   ║ Red  ║
+╚═══════╩══════╝
+                
+╔═══════╦══════╗
+║ Carol ║ Blue ║
+╠═══════╬══════╣
+║ Dave  ║ Blue ║
+╚═══════╩══════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!(table.render().lines().count(), table.rendered_size().1);
     }
 
     #[test]
-    fn rounded_table_style() {
-        let mut builder = Table::builder().style(TableStyle::rounded()).to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn section_break_first_and_last() {
+        let table = Table::builder()
+            .rows(rows![
+                Row::section_break(),
+                row!["Alice", "Red"],
+                row!["Bob", "Red"],
+                Row::section_break()
+            ])
+            .build();
 
-        let expected = r"╭─────────────────────────────────────────────────────────────────────────────────╮
-│                            This is some centered text                           │
-├────────────────────────────────────────┬────────────────────────────────────────┤
-│ This is left aligned text              │             This is right aligned text │
-├────────────────────────────────────────┼────────────────────────────────────────┤
-│ This is left aligned text              │             This is right aligned text │
-├────────────────────────────────────────┴────────────────────────────────────────┤
-│ This is some really really really really really really really really really tha │
-│ t is going to wrap to the next line                                             │
-╰─────────────────────────────────────────────────────────────────────────────────╯
+        let expected = r"               
+╔═══════╦═════╗
+║ Alice ║ Red ║
+╠═══════╬═════╣
+║ Bob   ║ Red ║
+╚═══════╩═════╝
+               
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!(table.render().lines().count(), table.rendered_size().1);
     }
 
     #[test]
-    fn complex_table() {
+    fn horizontal_fill() {
         let mut table = Table::builder()
+            .style(
+                TableStyle::builder()
+                    .based_on(TableStyle::simple())
+                    .horizontal_fill("=-")
+                    .build(),
+            )
             .rows(rows![
-                row![
-                    TableCell::builder("Col*1*Span*2").col_span(2),
-                    "Col 2 Span 1",
-                    TableCell::builder("Col 3 Span 2").col_span(2),
-                    "Col 4 Span 1"
-                ],
-                row![
-                    "Col 1 Span 1",
-                    "Col 2 Span 1",
-                    "Col 3 Span 1",
-                    TableCell::builder("Col 4 Span 2").col_span(2)
-                ],
-                row!["fasdaff", "fff", "fff",],
-                row![
-                    TableCell::builder("fasdff")
-                        .col_span(3)
-                        .alignment(Alignment::Right),
-                    TableCell::builder("fffdff").col_span(4),
-                ],
-                row!["fasdsaff", "fff", "f\nf\nf\nfff\nrrr\n\n\n",],
-                row!["fasdsaff",],
+                row!["Name", "Value"],
+                row![TableCell::builder("Spans both").col_span(2)]
             ])
             .build();
 
-        let s = table.render();
-
-        table.add_row(row![TableCell::builder(s)
-            .col_span(3)
-            .alignment(Alignment::Left)]);
+        let expected = r"+-=-=-=+=-=-=-=+
+| Name | Value |
++-=-=-=+=-=-=-=+
+| Spans both   |
++-=-=-=-=-=-=-=+
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
 
-        let expected = r"╔═════════════════════════════════════════════════════════╦════════════════════════════╦════════════════╦══════════════╦═══╗
-║ Col*1*Span*2                                            ║ Col 2 Span 1               ║ Col 3 Span 2   ║ Col 4 Span 1 ║   ║
-╠════════════════════════════╦════════════════════════════╬════════════════════════════╬════════════════╬══════════════╬═══╣
-║ Col 1 Span 1               ║ Col 2 Span 1               ║ Col 3 Span 1               ║ Col 4 Span 2   ║              ║   ║
-╠════════════════════════════╬════════════════════════════╬════════════════════════════╬═══════╦════════╬══════════════╬═══╣
-║ fasdaff                    ║ fff                        ║ fff                        ║       ║        ║              ║   ║
-╠════════════════════════════╩════════════════════════════╩════════════════════════════╬═══════╩════════╩══════════════╩═══╣
-║                                                                               fasdff ║ fffdff                            ║
-╠════════════════════════════╦════════════════════════════╦════════════════════════════╬═══════╦════════╦══════════════╦═══╣
-║ fasdsaff                   ║ fff                        ║ f                          ║       ║        ║              ║   ║
-║                            ║                            ║ f                          ║       ║        ║              ║   ║
-║                            ║                            ║ f                          ║       ║        ║              ║   ║
-║                            ║                            ║ fff                        ║       ║        ║              ║   ║
-║                            ║                            ║ rrr                        ║       ║        ║              ║   ║
-║                            ║                            ║                            ║       ║        ║              ║   ║
-║                            ║                            ║                            ║       ║        ║              ║   ║
-║                            ║                            ║                            ║       ║        ║              ║   ║
-╠════════════════════════════╬════════════════════════════╬════════════════════════════╬═══════╬════════╬══════════════╬═══╣
-║ fasdsaff                   ║                            ║                            ║       ║        ║              ║   ║
-╠════════════════════════════╩════════════════════════════╩════════════════════════════╬═══════╬════════╬══════════════╬═══╣
-║ ╔═════════════════════════════╦══════════════╦════════════════╦══════════════╦═══╗   ║       ║        ║              ║   ║
-║ ║ Col*1*Span*2                ║ Col 2 Span 1 ║ Col 3 Span 2   ║ Col 4 Span 1 ║   ║   ║       ║        ║              ║   ║
-║ ╠══════════════╦══════════════╬══════════════╬════════════════╬══════════════╬═══╣   ║       ║        ║              ║   ║
-║ ║ Col 1 Span 1 ║ Col 2 Span 1 ║ Col 3 Span 1 ║ Col 4 Span 2   ║              ║   ║   ║       ║        ║              ║   ║
-║ ╠══════════════╬══════════════╬══════════════╬═══════╦════════╬══════════════╬═══╣   ║       ║        ║              ║   ║
-║ ║ fasdaff      ║ fff          ║ fff          ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ╠══════════════╩══════════════╩══════════════╬═══════╩════════╩══════════════╩═══╣   ║       ║        ║              ║   ║
-║ ║                                     fasdff ║ fffdff                            ║   ║       ║        ║              ║   ║
-║ ╠══════════════╦══════════════╦══════════════╬═══════╦════════╦══════════════╦═══╣   ║       ║        ║              ║   ║
-║ ║ fasdsaff     ║ fff          ║ f            ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║ f            ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║ f            ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║ fff          ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║ rrr          ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║              ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║              ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ║              ║              ║              ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ╠══════════════╬══════════════╬══════════════╬═══════╬════════╬══════════════╬═══╣   ║       ║        ║              ║   ║
-║ ║ fasdsaff     ║              ║              ║       ║        ║              ║   ║   ║       ║        ║              ║   ║
-║ ╚══════════════╩══════════════╩══════════════╩═══════╩════════╩══════════════╩═══╝   ║       ║        ║              ║   ║
-║                                                                                      ║       ║        ║              ║   ║
-╚══════════════════════════════════════════════════════════════════════════════════════╩═══════╩════════╩══════════════╩═══╝
+        table.style.horizontal_fill = Some("==".into());
+        let expected = r"+======+=======+
+| Name | Value |
++======+=======+
+| Spans both   |
++==============+
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
     }
 
     #[test]
-    fn no_top_boarder() {
-        let mut builder = Table::builder()
-            .style(TableStyle::simple())
-            .has_top_boarder(false)
-            .to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn wide_horizontal_fill() {
+        let mut table = Table::builder()
+            .style(
+                TableStyle::builder()
+                    .based_on(TableStyle::simple())
+                    .horizontal_fill(String::from("＝"))
+                    .build(),
+            )
+            .rows(rows![
+                row!["Name", "Value"],
+                row![TableCell::builder("Spans both").col_span(2)]
+            ])
+            .build();
 
-        let expected = r"|                            This is some centered text                           |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is some really really really really really really really really really tha |
-| t is going to wrap to the next line                                             |
-+---------------------------------------------------------------------------------+
+        let expected = r"+-＝＝-+＝＝＝-+
+| Name | Value |
++-＝＝-+＝＝＝-+
+| Spans both   |
++-＝＝＝＝＝＝-+
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+
+        table.add_row(row![TableCell::builder("Spans down").row_span(2), "a"]);
+        table.add_row(row!["b"]);
+        let rendered = table.render();
+        println!("{}", rendered);
+        for line in rendered.lines() {
+            assert_eq!(22, string_width(line), "{}", line);
+        }
     }
 
     #[test]
-    fn no_bottom_boarder() {
-        let mut builder = Table::builder()
-            .style(TableStyle::simple())
-            .has_bottom_boarder(false)
-            .to_owned();
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+    fn rtl() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Age", "City"])
+            .rows(rows![
+                row!["Alice", 30, "Paris"],
+                row![TableCell::builder("Bob").col_span(2), "Rome"],
+                row!["Carol"]
+            ])
+            .build();
 
-        let expected = r"+---------------------------------------------------------------------------------+
-|                            This is some centered text                           |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is some really really really really really really really really really tha |
-| t is going to wrap to the next line                                             |
+        let expected = r"╔═══════╦═════╦═══════╗
+║ Name  ║ Age ║ City  ║
+╠═══════╬═════╬═══════╣
+║ Alice ║  30 ║ Paris ║
+╠═══════╩═════╬═══════╣
+║ Bob         ║ Rome  ║
+╠═══════╦═════╬═══════╣
+║ Carol ║     ║       ║
+╚═══════╩═════╩═══════╝
+";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+
+        table.rtl = true;
+        let expected = r"╔═══════╦═════╦═══════╗
+║  City ║ Age ║  Name ║
+╠═══════╬═════╬═══════╣
+║ Paris ║ 30  ║ Alice ║
+╠═══════╬═════╩═══════╣
+║  Rome ║         Bob ║
+╠═══════╬═════╦═══════╣
+║       ║     ║ Carol ║
+╚═══════╩═════╩═══════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!(
+            table.rendered_size(),
+            Table {
+                rtl: false,
+                ..table.clone()
+            }
+            .rendered_size()
+        );
+        assert_eq!("", Table::builder().rtl(true).build().render());
     }
 
     #[test]
-    fn no_separators() {
-        let mut builder = Table::builder()
-            .style(TableStyle::simple())
-            .separate_rows(false)
-            .to_owned();
+    fn render_canonical() {
+        let mut table = Table::builder()
+            .style(TableStyle::rounded())
+            .header_horizontal('━')
+            .header(row!["Name", "Qty"])
+            .rows(rows![
+                row![TableCell::builder("Apple").fg(Color::Red), 3],
+                row![TableCell::builder("Total")
+                    .col_span(2)
+                    .alignment(Alignment::Center)]
+                .with_style(TableStyle::heavy())
+            ])
+            .build();
 
-        add_data_to_test_table(&mut builder);
-        let table = builder.build();
+        let expected = r"+-------+-----+
+| Name  | Qty |
++-------+-----+
+| Apple |   3 |
++-------+-----+
+|    Total    |
++-------------+
+";
+        println!("{}", table.render_canonical());
+        assert_eq!(expected, table.render_canonical());
 
-        let expected = r"+---------------------------------------------------------------------------------+
-|                            This is some centered text                           |
-| This is left aligned text              |             This is right aligned text |
-| This is left aligned text              |             This is right aligned text |
-| This is some really really really really really really really really really tha |
-| t is going to wrap to the next line                                             |
-+---------------------------------------------------------------------------------+
+        table.style = TableStyle::thin();
+        table.line_ending = LineEnding::CrLf;
+        assert_eq!(expected, table.render_canonical());
+    }
+
+    #[test]
+    fn natural_width() {
+        let cells = vec![
+            TableCell::new(""),
+            TableCell::new("Hello\nWide 世界 line\nend"),
+            TableCell::new("\x1b[31mred\x1b[0m text\n\x1b[1mbold\x1b[0m"),
+            TableCell::new("a\tb\tc\nabcde\tf"),
+            TableCell::new("one\r\ntwo three"),
+            TableCell::builder("one\r\ntwo three")
+                .wrap_style(WrapStyle::Word)
+                .build(),
+            TableCell::builder("x\u{00a7}y")
+                .ambiguous_wide(true)
+                .build(),
+            TableCell::builder("truncated line\nx")
+                .wrap_mode(WrapMode::Truncate)
+                .padding(2)
+                .build(),
+            TableCell::builder("no padding").pad_content(false).build(),
+            TableCell::verbatim("  art  \n   more art"),
+        ];
+        for cell in cells {
+            assert_eq!(cell.width(), cell.natural_width(), "{:?}", cell.data);
+        }
+    }
+
+    #[test]
+    fn fixed_column_widths() {
+        let table = Table::builder()
+            .fixed_column_width(0, 15)
+            .rows(rows![
+                row!["Short", "x"],
+                row!["This content is longer than the column", "y"]
+            ])
+            .build();
+
+        let expected = r"╔═══════════════╦═══╗
+║ Short         ║ x ║
+╠═══════════════╬═══╣
+║ This content  ║ y ║
+║ is longer tha ║   ║
+║ n the column  ║   ║
+╚═══════════════╩═══╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!(vec![15, 3], table.column_widths());
     }
 
     #[test]
-    fn some_rows_no_separators() {
-        let mut builder = Table::builder().style(TableStyle::simple()).to_owned();
-        add_data_to_test_table(&mut builder);
-        let mut table = builder.build();
+    fn swap_rows() {
+        let mut table = Table::builder()
+            .header(row!["Name"])
+            .rows(rows![row!["a"], row!["b"]])
+            .build();
+        assert!(table.swap_rows(1, 2));
+        assert_eq!(vec![row!["Name"], row!["b"], row!["a"]], table.rows);
+        assert!(!table.swap_rows(0, 3));
+        assert_eq!(vec![row!["Name"], row!["b"], row!["a"]], table.rows);
+    }
 
-        table.rows[2].has_separator = false;
+    #[test]
+    fn swap_columns() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Age", "City"])
+            .rows(rows![row!["Alice", 30, "Paris"]])
+            .build();
+        table.column_alignments.insert(0, Alignment::Center);
+        assert!(table.swap_columns(0, 2));
+        assert_eq!(
+            vec![row!["City", "Age", "Name"], row!["Paris", 30, "Alice"]],
+            table.rows
+        );
+        assert_eq!(Some(&Alignment::Center), table.column_alignments.get(&2));
+        assert!(!table.swap_columns(0, 3));
 
-        let expected = r"+---------------------------------------------------------------------------------+
-|                            This is some centered text                           |
-+----------------------------------------+----------------------------------------+
-| This is left aligned text              |             This is right aligned text |
-| This is left aligned text              |             This is right aligned text |
-+----------------------------------------+----------------------------------------+
-| This is some really really really really really really really really really tha |
-| t is going to wrap to the next line                                             |
-+---------------------------------------------------------------------------------+
+        // Rows where a cell spans one of the columns are left unchanged
+        let mut table = Table::builder()
+            .rows(rows![
+                row!["a", "b", "c"],
+                row![TableCell::builder("ab").col_span(2), "c"],
+                row![TableCell::builder("a").row_span(2), "b", "c"],
+                row!["b", "c"]
+            ])
+            .build();
+        assert!(table.swap_columns(0, 2));
+        let expected = r"╔═══╦═══╦═══╗
+║ c ║ b ║ a ║
+╠═══╩═══╬═══╣
+║ ab    ║ c ║
+╠═══╦═══╬═══╣
+║ a ║ b ║ c ║
+║   ╠═══╬═══╣
+║   ║ b ║ c ║
+╚═══╩═══╩═══╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
     }
 
     #[test]
-    fn colored_data_works() {
+    fn width_override() {
         let table = Table::builder()
-            .rows(rows![row![TableCell::builder("\u{1b}[31ma\u{1b}[0m")]])
+            .rows(rows![
+                row![TableCell::builder("Name").width_override(20), "Age"],
+                row!["Alice", TableCell::builder(30).width_override(4)],
+                row!["Bob", TableCell::builder(25).width_override(6)]
+            ])
             .build();
-        let expected = "╔═══╗
-║ \u{1b}[31ma\u{1b}[0m ║
-╚═══╝
+
+        let expected = r"╔════════════════════╦══════╗
+║ Name               ║ Age  ║
+╠════════════════════╬══════╣
+║ Alice              ║ 30   ║
+╠════════════════════╬══════╣
+║ Bob                ║ 25   ║
+╚════════════════════╩══════╝
 ";
         println!("{}", table.render());
         assert_eq!(expected, table.render());
+        assert_eq!(vec![20, 6], table.column_widths());
+    }
+
+    #[test]
+    fn expand_to_width() {
+        let mut table = Table::builder()
+            .header(row!["Name", "Description", "Qty"])
+            .rows(rows![row!["Apple", "A round and red fruit.", 3]])
+            .build();
+        assert_eq!(vec![7, 24, 5], table.column_widths());
+        assert_eq!(40, table.rendered_size().0);
+        table.expand_to_width(80);
+        assert_eq!(vec![14, 52, 10], table.column_widths());
+        assert_eq!(80, table.rendered_size().0);
+
+        let mut table = Table::builder()
+            .header(row!["Name", "Description", "Qty"])
+            .rows(rows![row!["Apple", "A round and red fruit.", 3]])
+            .build();
+        table.set_max_width_for_column(2, 5);
+        table.expand_to_width(80);
+        assert_eq!(vec![16, 55, 5], table.column_widths());
+        assert_eq!(80, table.rendered_size().0);
+    }
+
+    #[test]
+    fn iter_rendered() {
+        let mut builder = Table::builder();
+        add_data_to_test_table(&mut builder);
+        let mut table = builder.build();
+
+        let rendered: Vec<(RowPosition, String)> = table.iter_rendered().collect();
+        assert_eq!(5, rendered.len());
+        assert!(rendered[0].0 == RowPosition::First);
+        assert!(rendered[1..4]
+            .iter()
+            .all(|(position, _)| *position == RowPosition::Mid));
+        assert!(rendered[4].0 == RowPosition::Last);
+        assert_eq!(
+            "╚═════════════════════════════════════════════════════════════════════════════════╝\n",
+            rendered[4].1
+        );
+        let joined: String = rendered.into_iter().map(|(_, lines)| lines).collect();
+        assert_eq!(table.render(), joined);
+
+        table.has_bottom_boarder = false;
+        let rendered: Vec<(RowPosition, String)> = table.iter_rendered().collect();
+        assert_eq!(4, rendered.len());
+        assert!(rendered[3].0 == RowPosition::Last);
+    }
+
+    #[test]
+    fn trim_trailing_whitespace() {
+        let mut table = Table::builder()
+            .style(TableStyle::blank())
+            .trim_trailing_whitespace(true)
+            .rows(rows![
+                row!["Name", "Description"],
+                row!["Apple", "A red fruit"],
+                row!["Kiwi", "Small"]
+            ])
+            .build();
+
+        // The blank style draws its borders with null characters
+        let expected =
+            "\n\0 Name  \0 Description\n\n\0 Apple \0 A red fruit\n\n\0 Kiwi  \0 Small\n\n";
+        println!("{}", table.render());
+        assert_eq!(expected, table.render());
+        assert!(table.render().lines().all(|line| !line.ends_with(' ')));
+
+        table.style = TableStyle::simple();
+        assert!(table
+            .render()
+            .lines()
+            .all(|line| line.ends_with('|') || line.ends_with('+')));
+    }
+
+    #[test]
+    fn layout() {
+        let mut table = Table::builder()
+            .rows(rows![row!(TableCell::builder("My Lucky Numbers")
+                .alignment(Alignment::Center)
+                .col_span(6))])
+            .style(TableStyle::elegant())
+            .build();
+        for draw in 0..5 {
+            let mut row = Row::empty();
+            for number in 0..6 {
+                row.add_cell(TableCell::new((draw * 6 + number + 10).to_string()));
+            }
+            table.add_row(row);
+        }
+
+        let layout = table.layout();
+        println!("{}", table.render());
+        assert_eq!(
+            Layout {
+                column_widths: vec![4; 6],
+                row_heights: vec![1; 6],
+                total_width: 31,
+                total_height: 13,
+            },
+            layout
+        );
+        assert_eq!(table.render().lines().count(), layout.total_height);
+        assert_eq!(
+            (layout.total_width, layout.total_height),
+            table.rendered_size()
+        );
     }
 
     fn add_data_to_test_table(builder: &mut TableBuilder) {
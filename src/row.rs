@@ -1,14 +1,51 @@
-use crate::table_cell::{string_width, Alignment, TableCell};
-use crate::{RowPosition, TableStyle};
-use std::cmp::max;
+use crate::table_cell::{display_width, Alignment, CenterBias, TableCell, VerticalAlignment};
+use crate::{RowPosition, Table, TableStyle};
+use std::cmp::{max, min};
 use unicode_width::UnicodeWidthChar;
 
+/// Settings of a table that affect how each of its rows are drawn
+#[derive(Clone, Copy)]
+pub(crate) struct FormatOptions<'a> {
+    /// The style used for the edges of the row
+    pub outer: &'a TableStyle,
+    /// The style used between the cells of the row
+    pub inner: &'a TableStyle,
+    /// The number of blank lines above and below the content of the row
+    pub row_padding: usize,
+    pub has_left_border: bool,
+    pub has_right_border: bool,
+    /// Whether columns are separated by the vertical character of the inner style rather than spaces
+    pub separate_columns: bool,
+}
+
+impl<'a> FormatOptions<'a> {
+    /// Options for drawing a row with all of its borders and no padding
+    pub fn new(outer: &'a TableStyle, inner: &'a TableStyle) -> FormatOptions<'a> {
+        FormatOptions {
+            outer,
+            inner,
+            row_padding: 0,
+            has_left_border: true,
+            has_right_border: true,
+            separate_columns: true,
+        }
+    }
+}
+
 /// A set of table cells
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Row {
     pub cells: Vec<TableCell>,
     /// Whether the row should have a top boarder or not
     pub has_separator: bool,
+    /// The default alignment of the row's cells. Used by cells that don't have an alignment of their own,
+    /// taking precedence over the default alignments of the table
+    pub alignment: Option<Alignment>,
+    /// The style used for the row and the separator above it instead of the table's style
+    pub style: Option<TableStyle>,
+    /// Whether the row is a section break, which is drawn as a blank line across the table instead of its cells
+    pub section_break: bool,
 }
 
 impl Row {
@@ -20,6 +57,9 @@ impl Row {
         let mut row = Row {
             cells: vec![],
             has_separator: true,
+            alignment: None,
+            style: None,
+            section_break: false,
         };
 
         for entry in cells.into_iter() {
@@ -33,6 +73,21 @@ impl Row {
         Row {
             cells: vec![],
             has_separator: true,
+            alignment: None,
+            style: None,
+            section_break: false,
+        }
+    }
+
+    /// Creates a row that is drawn as a blank line across the whole table, without any borders.
+    ///
+    /// Use it to visually separate groups of rows. The rows on either side of the break are drawn with their own
+    /// top and bottom borders, like separate tables that share column widths. It doesn't affect the widths of the columns
+    pub fn section_break() -> Row {
+        Row {
+            has_separator: false,
+            section_break: true,
+            ..Row::empty()
         }
     }
 
@@ -46,39 +101,122 @@ impl Row {
         row
     }
 
+    /// The options used to draw the row, which use the row's style when it has one
+    fn own_options<'a>(&'a self, options: &FormatOptions<'a>) -> FormatOptions<'a> {
+        match &self.style {
+            Some(style) => FormatOptions {
+                outer: style,
+                inner: style,
+                ..*options
+            },
+            None => *options,
+        }
+    }
+
+    /// The number of lines taken up by the tallest cell of the row once its content is wrapped to the column widths.
+    ///
+    /// This doesn't include the table's `row_padding`
+    pub fn height(&self, column_widths: &[usize]) -> usize {
+        let col_spans = self.col_spans(column_widths);
+        self.wrapped_cells(column_widths, &col_spans)
+            .iter()
+            .map(Vec::len)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The number of columns spanned by each cell
+    ///
+    /// Cells can't span past the last column so their spans are cut off at it,
+    /// and a col_span of 0 is treated as 1
+    fn col_spans(&self, column_widths: &[usize]) -> Vec<usize> {
+        let mut remaining_columns = column_widths.len();
+        self.cells
+            .iter()
+            .map(|cell| {
+                let col_span = min(max(cell.col_span, 1), remaining_columns);
+                remaining_columns -= col_span;
+                col_span
+            })
+            .collect()
+    }
+
+    /// Wraps the content of each cell to the total width of the columns it spans
+    fn wrapped_cells(&self, column_widths: &[usize], col_spans: &[usize]) -> Vec<Vec<String>> {
+        let mut spanned_columns = 0;
+        let mut wrapped_cells = Vec::new();
+        for (cell, &col_span) in self.cells.iter().zip(col_spans) {
+            if col_span == 0 {
+                break;
+            }
+            let width: usize = column_widths[spanned_columns..spanned_columns + col_span]
+                .iter()
+                .sum();
+            // Wrap to the total width - col_span to account for separators
+            wrapped_cells.push(cell.wrapped_content(width + col_span - 1));
+            spanned_columns += col_span;
+        }
+        wrapped_cells
+    }
+
+    /// Sets the default alignment of the row's cells
+    pub fn with_alignment(mut self, alignment: Alignment) -> Row {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    /// Sets the style used for the row and the separator above it instead of the table's style
+    pub fn with_style(mut self, style: TableStyle) -> Row {
+        self.style = Some(style);
+        self
+    }
+
     /// Formats a row based on the provided table style
     pub fn format(&self, column_widths: &[usize], style: &TableStyle) -> String {
+        self.format_with_styles(column_widths, style, style)
+    }
+
+    /// Formats a row using `outer` for the vertical lines at the edges of the row
+    /// and `inner` for the vertical lines between its cells
+    pub fn format_with_styles(
+        &self,
+        column_widths: &[usize],
+        outer: &TableStyle,
+        inner: &TableStyle,
+    ) -> String {
+        self.format_with_options(column_widths, &FormatOptions::new(outer, inner))
+    }
+
+    /// Formats a row using the settings of the table it belongs to
+    #[allow(clippy::needless_range_loop)]
+    pub(crate) fn format_with_options(
+        &self,
+        column_widths: &[usize],
+        options: &FormatOptions,
+    ) -> String {
+        let options = &self.own_options(options);
+        let FormatOptions {
+            outer,
+            inner,
+            row_padding,
+            ..
+        } = *options;
         let mut buf = String::new();
 
+        let col_spans = self.col_spans(column_widths);
+        let wrapped_cells = self.wrapped_cells(column_widths, &col_spans);
+
+        // The height of the row determined by how many times a cell had to wrap
+        let mut row_height = wrapped_cells.iter().map(Vec::len).max().unwrap_or(0);
+
         // Since a cell can span multiple columns we need to track
         // how many columns we have actually spanned. We cannot just depend
         // on the index of the current cell when iterating
         let mut spanned_columns = 0;
 
-        // The height of the row determined by how many times a cell had to wrap
-        let mut row_height = 0;
-
-        // Wrapped cell content
-        let mut wrapped_cells = Vec::new();
-
-        // The first thing we do is wrap the cells if their
-        // content is greater than the max width of the column they are in
-        for cell in &self.cells {
-            let mut width = 0;
-            // Iterate from 0 to the cell's col_span and add up all the max width
-            // values for each column so we can properly pad the cell content later
-            for j in 0..cell.col_span {
-                width += column_widths[j + spanned_columns];
-            }
-            // Wrap to the total width - col_span to account for separators
-            let wrapped_cell = cell.wrapped_content(width + cell.col_span - 1);
-            row_height = max(row_height, wrapped_cell.len());
-            wrapped_cells.push(wrapped_cell);
-            spanned_columns += cell.col_span;
-        }
-
-        // reset spanned_columns so we can reuse it in the next loop
-        spanned_columns = 0;
+        // Blank lines are added above and below the tallest cell's content
+        let content_area = row_height;
+        row_height += row_padding * 2;
 
         // Row lines to combine into the final string at the end
         let mut lines = vec![String::new(); row_height];
@@ -87,6 +225,15 @@ impl Row {
         // We may not have as many cells as column widths, or the cells may not even span
         // as many columns as are in column widths. In that case weill will create empty cells
         for col_idx in 0..column_widths.len() {
+            let vertical = if col_idx > 0 && !options.separate_columns {
+                " ".to_string()
+            } else if col_idx > 0 {
+                inner.column_separator.to_string()
+            } else if options.has_left_border {
+                outer.vertical.to_string()
+            } else {
+                String::new()
+            };
             // Check to see if we actually have a cell for the column index
             // Otherwise we will just need to print out empty space as filler
             if self.cells.len() > col_idx {
@@ -101,67 +248,81 @@ impl Row {
                 // If there are fewer cells than columns but they span the total number of columns we just break out
                 // of the outer for loop at the end. We know how many cells we've spanned by adding the cell's col_span to spanned_columns
                 let cell = &self.cells[col_idx];
+                let col_span = col_spans[col_idx];
                 // Calculate the cell span by adding up the widths of the columns spanned by the cell
-                for c in 0..cell.col_span {
+                for c in 0..col_span {
                     cell_span += column_widths[spanned_columns + c];
                 }
+                // Number of blank lines to draw above the cell's content so it is vertically aligned
+                let content_height = wrapped_cells[col_idx].len();
+                let top_offset = row_padding
+                    + match cell.vertical_alignment {
+                        VerticalAlignment::Top => 0,
+                        VerticalAlignment::Middle => (content_area - content_height) / 2,
+                        VerticalAlignment::Bottom => content_area - content_height,
+                    };
                 // Since cells can wrap we need to loop over all of the lines
                 for (line_idx, line) in lines.iter_mut().enumerate().take(row_height) {
                     // Check to see if the wrapped cell has a line for the line index
-                    if wrapped_cells[col_idx].len() > line_idx {
+                    if line_idx >= top_offset && line_idx - top_offset < content_height {
+                        let content_idx = line_idx - top_offset;
                         // We may need to pad the cell if it's contents are not as wide as some other cell in the column
-                        let mut padding = 0;
                         // We need to calculate the string_width because some characters take up extra space and we need to
                         // ignore ANSI characters
-                        let str_width = string_width(&wrapped_cells[col_idx][line_idx]);
-                        if cell_span >= str_width {
-                            padding += cell_span - str_width;
-                            // If the cols_span is greater than one we need to add extra padding for the missing vertical characters
-                            if cell.col_span > 1 {
-                                padding += style.vertical.width().unwrap_or_default()
-                                    * (cell.col_span - 1); // Subtract one since we add a vertical character to the beginning
-                            }
+                        let str_width = display_width(
+                            &wrapped_cells[col_idx][content_idx],
+                            cell.ambiguous_wide,
+                        );
+                        let mut available = cell_span;
+                        // If the cols_span is greater than one we need to add extra padding for the missing vertical characters
+                        if col_span > 1 {
+                            available +=
+                                inner.column_separator.width().unwrap_or_default() * (col_span - 1);
+                            // Subtract one since we add a vertical character to the beginning
                         }
+                        // We may need to pad the cell if it's contents are not as wide as some other cell in the column
+                        let padding = available.saturating_sub(str_width);
+
+                        // Justified text would look stretched on the last line so it is left aligned instead
+                        let alignment = match cell.alignment {
+                            Alignment::Justify if content_idx + 1 == content_height => {
+                                Alignment::Left
+                            }
+                            alignment => alignment,
+                        };
 
                         // Finally we can push the string into the lines vec
                         line.push_str(
                             format!(
                                 "{}{}",
-                                style.vertical,
-                                self.pad_string(
+                                vertical,
+                                cell.colorize(self.pad_string(
                                     padding,
-                                    cell.alignment,
-                                    &wrapped_cells[col_idx][line_idx]
-                                )
+                                    alignment,
+                                    cell.center_bias,
+                                    &wrapped_cells[col_idx][content_idx]
+                                ))
                             )
                             .as_str(),
                         );
                     } else {
                         // If the cell doesn't have any content for this line just fill it with empty space
                         line.push_str(
-                            format!(
-                                "{}{}",
-                                style.vertical,
-                                str::repeat(
-                                    " ",
-                                    column_widths[spanned_columns] * cell.col_span + cell.col_span
-                                        - 1
-                                )
-                            )
-                            .as_str(),
+                            format!("{}{}", vertical, str::repeat(" ", cell_span + col_span - 1))
+                                .as_str(),
                         );
                     }
                 }
                 // Keep track of how many columns we have actually spanned since
                 // cells can be wider than a single column
-                spanned_columns += cell.col_span;
+                spanned_columns += col_span;
             } else {
                 // If we don't have a cell for the coulumn then we just create an empty one
                 for line in lines.iter_mut().take(row_height) {
                     line.push_str(
                         format!(
                             "{}{}",
-                            style.vertical,
+                            vertical,
                             str::repeat(" ", column_widths[spanned_columns])
                         )
                         .as_str(),
@@ -178,7 +339,9 @@ impl Row {
         // Finally add all the lines together to create the row content
         for line in &lines {
             buf.push_str(line.clone().as_str());
-            buf.push(style.vertical);
+            if options.has_right_border {
+                buf.push(outer.vertical);
+            }
             buf.push('\n');
         }
         buf.pop();
@@ -196,6 +359,67 @@ impl Row {
         row_position: RowPosition,
         previous_separator: Option<String>,
     ) -> String {
+        self.gen_separator_with_styles(
+            column_widths,
+            style,
+            style,
+            row_position,
+            previous_separator,
+        )
+    }
+
+    /// Generates the top separator for a row using `outer` for the top and bottom borders
+    /// and the ends of the separator, and `inner` for the rest of it.
+    ///
+    /// The previous seperator is expected to have been generated with the same styles
+    pub fn gen_separator_with_styles(
+        &self,
+        column_widths: &[usize],
+        outer: &TableStyle,
+        inner: &TableStyle,
+        row_position: RowPosition,
+        previous_separator: Option<String>,
+    ) -> String {
+        let options = FormatOptions::new(outer, inner);
+        let style = self.separator_style(&options, row_position);
+        let separator = self.gen_separator_with_options(
+            column_widths,
+            &options,
+            row_position,
+            previous_separator.map(|prev| style.unfill_horizontal(&prev)),
+        );
+        style.fill_horizontal(&separator)
+    }
+
+    /// The style used for the horizontal lines of the separator above the row
+    pub(crate) fn separator_style<'a>(
+        &'a self,
+        options: &FormatOptions<'a>,
+        row_position: RowPosition,
+    ) -> &'a TableStyle {
+        let options = self.own_options(options);
+        // Separators inside the table use the inner style, apart from where they meet the outer border
+        if row_position == RowPosition::Mid {
+            options.inner
+        } else {
+            options.outer
+        }
+    }
+
+    /// Generates the top separator for a row using the settings of the table it belongs to.
+    ///
+    /// The separator is drawn with `horizontal` rather than `horizontal_fill` so that it can still be
+    /// merged with other separators. `TableStyle::fill_horizontal` is applied to it once it is complete
+    pub(crate) fn gen_separator_with_options(
+        &self,
+        column_widths: &[usize],
+        options: &FormatOptions,
+        row_position: RowPosition,
+        previous_separator: Option<String>,
+    ) -> String {
+        let style = self.separator_style(options, row_position);
+        let options = &self.own_options(options);
+        let outer = options.outer;
         let mut buf = String::new();
 
         // If the first cell has a col_span > 1 we need to set the next
@@ -206,14 +430,20 @@ impl Row {
         };
 
         // Push the initial char for the row
-        buf.push(style.start_for_position(row_position));
+        if options.has_left_border {
+            buf.push(outer.start_for_position(row_position));
+        }
 
         let mut current_column = 0;
 
         for (i, column_width) in column_widths.iter().enumerate() {
             if i == next_intersection {
                 // Draw the intersection character for the start of the column
-                buf.push(style.intersect_for_position(row_position));
+                if options.separate_columns {
+                    buf.push(style.intersect_for_position(row_position));
+                } else {
+                    buf.push(style.horizontal);
+                }
 
                 current_column += 1;
 
@@ -235,16 +465,18 @@ impl Row {
             );
         }
 
-        buf.push(style.end_for_position(row_position));
+        if options.has_right_border {
+            buf.push(outer.end_for_position(row_position));
+        }
 
         let mut out = String::new();
 
         // Merge the previous seperator string with the current buffer
         // This will handle cases where a cell above/below has a different col_span value
-        match previous_separator {
+        let out = match previous_separator {
             Some(prev) => {
                 for pair in buf.chars().zip(prev.chars()) {
-                    if pair.0 == style.outer_left_vertical || pair.0 == style.outer_right_vertical {
+                    if pair.0 == outer.outer_left_vertical || pair.0 == outer.outer_right_vertical {
                         // Always take the start and end characters of the current buffer
                         out.push(pair.0);
                     } else if pair.0 != style.horizontal || pair.1 != style.horizontal {
@@ -260,7 +492,9 @@ impl Row {
                 out
             }
             None => buf,
-        }
+        };
+
+        out
     }
 
     /// Returns a vector of split cell widths.
@@ -291,24 +525,64 @@ impl Row {
 
     /// Number of columns in the row.
     ///
-    /// This is the sum of all cell's col_span values
+    /// This is the sum of all cell's col_span values, counting a col_span of 0 as 1
     pub fn num_columns(&self) -> usize {
-        self.cells.iter().map(|x| x.col_span).sum()
+        self.cells.iter().map(|x| max(x.col_span, 1)).sum()
+    }
+
+    /// Whether every cell in the row is empty or only contains whitespace
+    pub fn is_empty(&self) -> bool {
+        self.cells.iter().all(|cell| cell.data.trim().is_empty())
     }
 
     /// Pads a string accoding to the provided alignment
-    fn pad_string(&self, padding: usize, alignment: Alignment, text: &str) -> String {
+    fn pad_string(
+        &self,
+        padding: usize,
+        alignment: Alignment,
+        center_bias: CenterBias,
+        text: &str,
+    ) -> String {
         match alignment {
-            Alignment::Left => return format!("{}{}", text, str::repeat(" ", padding)),
-            Alignment::Right => return format!("{}{}", str::repeat(" ", padding), text),
+            Alignment::Left => format!("{}{}", text, str::repeat(" ", padding)),
+            // Decimal points are lined up by the table, which leaves the cells right aligned
+            Alignment::Right | Alignment::Decimal => {
+                format!("{}{}", str::repeat(" ", padding), text)
+            }
             Alignment::Center => {
-                let half_padding = padding as f32 / 2.0;
-                return format!(
+                let (left, right) = match center_bias {
+                    CenterBias::Left => (padding - padding / 2, padding / 2),
+                    CenterBias::Right => (padding / 2, padding - padding / 2),
+                };
+                format!(
                     "{}{}{}",
-                    str::repeat(" ", half_padding.ceil() as usize),
+                    str::repeat(" ", left),
                     text,
-                    str::repeat(" ", half_padding.floor() as usize)
-                );
+                    str::repeat(" ", right)
+                )
+            }
+            Alignment::Justify => {
+                // Keep the cell's padding in place and only stretch the gaps between the words
+                let is_pad = |c: char| c == ' ' || c == '\0';
+                let content = text.trim_matches(is_pad);
+                let words: Vec<&str> = content.split(' ').collect();
+                if content.is_empty() || words.len() < 2 {
+                    return self.pad_string(padding, Alignment::Left, center_bias, text);
+                }
+                let start = text.len() - text.trim_start_matches(is_pad).len();
+                let end = text.trim_end_matches(is_pad).len();
+
+                let gaps = words.len() - 1;
+                let mut res = text[..start].to_string();
+                for (i, word) in words.iter().enumerate() {
+                    res.push_str(word);
+                    if i < gaps {
+                        let extra = padding / gaps + usize::from(i < padding % gaps);
+                        res.push_str(&str::repeat(" ", extra + 1));
+                    }
+                }
+                res.push_str(&text[end..]);
+                res
             }
         }
     }
@@ -318,4 +592,40 @@ impl Row {
         self.cells.push(cell);
     }
 
+    /// Returns the cell occupying a column.
+    ///
+    /// Cells spanning multiple columns are returned for each column they span
+    pub fn cell_at_column(&self, column: usize) -> Option<&TableCell> {
+        self.cell_index_at_column(column).map(|i| &self.cells[i])
+    }
+
+    /// Returns a mutable reference to the cell occupying a column.
+    ///
+    /// Cells spanning multiple columns are returned for each column they span
+    pub fn cell_at_column_mut(&mut self, column: usize) -> Option<&mut TableCell> {
+        self.cell_index_at_column(column)
+            .map(move |i| &mut self.cells[i])
+    }
+
+    /// The index of the cell occupying a column
+    fn cell_index_at_column(&self, column: usize) -> Option<usize> {
+        let mut spanned_columns = 0;
+        for (i, cell) in self.cells.iter().enumerate() {
+            spanned_columns += cell.col_span;
+            if column < spanned_columns {
+                return Some(i);
+            }
+        }
+        None
+    }
+}
+
+/// Renders the row on its own as a table with the extended style, with each column as wide as its cell.
+///
+/// This is meant for debugging. Use `Table` to render rows with columns that line up
+impl std::fmt::Display for Row {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let table = Table::builder().rows(vec![self.clone()]).build();
+        write!(f, "{}", table.render())
+    }
 }
@@ -1,30 +1,191 @@
 use lazy_static;
 use regex::Regex;
+use std::borrow::Cow;
 use std::cmp;
 use std::collections::HashSet;
 
-use unicode_width::UnicodeWidthChar;
+use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
 /// Represents the horizontal alignment of content within a cell.
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum Alignment {
     Left,
     Right,
     Center,
+    /// Extra space is spread evenly between words.
+    ///
+    /// The last line of a cell is left aligned
+    Justify,
+    /// The decimal points of the numbers in a column are lined up.
+    ///
+    /// Content without a decimal point is aligned as if it ended with one
+    Decimal,
+}
+
+/// The side of a center aligned cell that gets the extra space when the space around its content is uneven.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum CenterBias {
+    /// The extra space is added before the content, moving it to the right
+    Left,
+    /// The extra space is added after the content, moving it to the left
+    Right,
+}
+
+/// Determines how a cell's content is handled when it is wider than its column.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WrapMode {
+    /// Content is wrapped onto as many lines as needed
+    Wrap,
+    /// Content is cut off and marked with an ellipsis (`…`). Where it's cut off is set by the cell's `Truncation`
+    Truncate,
+    /// Content is never wrapped. The column is widened to fit it, even beyond its max width
+    NoWrap,
+}
+
+/// Determines which part of a cell's content is cut off when it's truncated.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Truncation {
+    /// The end of the content is cut off, as in `long te…`
+    End,
+    /// The start of the content is cut off, as in `…ng text`
+    Start,
+    /// The middle of the content is cut off, as in `lon…ext`
+    Middle,
+}
+
+/// Determines where content is broken when it is wrapped.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum WrapStyle {
+    /// Lines are broken at any character
+    Character,
+    /// Lines are broken between words when possible.
+    /// Words that are wider than the column are still broken at any character
+    Word,
+}
+
+/// Represents the vertical alignment of content within a cell.
+///
+/// This only has an effect when another cell in the same row is taller.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum VerticalAlignment {
+    Top,
+    Middle,
+    Bottom,
+}
+
+/// A terminal color used for the foreground or background of a cell's content.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Color {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    /// A color from the 256 color palette
+    Fixed(u8),
+    /// A 24-bit color
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    /// The SGR escape sequence that applies the color. `base` is 30 for foreground colors and 40 for background colors
+    fn sgr(self, base: u8) -> String {
+        match self {
+            Color::Black => format!("\x1b[{}m", base),
+            Color::Red => format!("\x1b[{}m", base + 1),
+            Color::Green => format!("\x1b[{}m", base + 2),
+            Color::Yellow => format!("\x1b[{}m", base + 3),
+            Color::Blue => format!("\x1b[{}m", base + 4),
+            Color::Magenta => format!("\x1b[{}m", base + 5),
+            Color::Cyan => format!("\x1b[{}m", base + 6),
+            Color::White => format!("\x1b[{}m", base + 7),
+            Color::Fixed(n) => format!("\x1b[{};5;{}m", base + 8, n),
+            Color::Rgb(r, g, b) => format!("\x1b[{};2;{};{};{}m", base + 8, r, g, b),
+        }
+    }
 }
 
 ///A table cell containing some str data.
 ///
 ///A cell may span multiple columns by setting the value of `col_span`.
 ///
-///`pad_content` will add a space to either side of the cell's content.AsRef
-#[derive(Debug, Clone)]
+///`left_padding` and `right_padding` add that many spaces before and after the cell's content
+///when `pad_content` is true.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TableCell {
     pub data: String,
     pub col_span: usize,
+    /// The number of rows the cell occupies.
+    ///
+    /// Cells in the rows below are shifted to the right of the columns the cell occupies
+    pub row_span: usize,
+    /// The horizontal alignment of the cell's content
     pub alignment: Alignment,
+    /// Whether the cell uses the default alignment of its row, column or the header instead of `alignment`.
+    ///
+    /// `alignment` is still used when none of them has a default. `TableCellBuilder::alignment` turns this off
+    pub inherit_alignment: bool,
+    pub vertical_alignment: VerticalAlignment,
+    pub wrap_mode: WrapMode,
+    pub wrap_style: WrapStyle,
+    /// Whether `left_padding` and `right_padding` are added around the cell's content.
+    ///
+    /// Content without padding is drawn right up to the borders of the cell
     pub pad_content: bool,
+    /// The number of spaces added before the cell's content
+    pub left_padding: usize,
+    /// The number of spaces added after the cell's content
+    pub right_padding: usize,
+    /// The color of the cell's content
+    pub fg: Option<Color>,
+    /// The color behind the cell's content
+    pub bg: Option<Color>,
+    /// The number of columns between tab stops. Tab characters are replaced with spaces up to the next tab stop
+    pub tab_width: usize,
+    /// Whether the cell's content should be rendered exactly as it was provided.
+    ///
+    /// Verbatim content is never padded, wrapped or has its tabs expanded, and the column is widened to fit its widest line
+    pub verbatim: bool,
+    /// The side of the cell that gets the extra space when center aligned content can't be centered exactly
+    pub center_bias: CenterBias,
+    /// Whether East Asian characters of ambiguous width, such as `§`, are two columns wide instead of one.
+    ///
+    /// Terminals using a CJK locale usually draw these characters two columns wide
+    pub ambiguous_wide: bool,
+    /// Where content is cut off when the wrap mode is `WrapMode::Truncate`
+    pub truncation: Truncation,
+    /// Marks where content was cut off when the wrap mode is `WrapMode::Truncate`. Defaults to `…`
+    pub ellipsis: String,
+    /// Whether words that are too wide for a line are broken with a hyphen when the wrap style is `WrapStyle::Word`.
+    /// Words are broken after the hyphens they already contain when possible
+    pub hyphenate: bool,
+    /// An arbitrary label, such as the ID of the record the cell shows.
+    /// It isn't rendered, so it can be used to map a rendered cell back to its source
+    pub tag: Option<String>,
+    /// The width of the column the cell is in, including padding, regardless of the content of the column.
+    ///
+    /// When cells in the same column set different widths the widest is used, and the table's `fixed_column_widths`
+    /// take precedence over all of them. Cells spanning multiple columns don't change their widths
+    pub width_override: Option<usize>,
 }
 
 impl TableCell {
@@ -32,11 +193,81 @@ impl TableCell {
     where
         T: ToString,
     {
+        Self::from_string(data.to_string())
+    }
+
+    /// Creates a cell that takes ownership of `data` instead of copying it
+    pub fn from_string(data: String) -> TableCell {
         Self {
-            data: data.to_string(),
+            data,
             col_span: 1,
+            row_span: 1,
             alignment: Alignment::Left,
+            inherit_alignment: true,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
             pad_content: true,
+            left_padding: 1,
+            right_padding: 1,
+            verbatim: false,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
+        }
+    }
+
+    /// Creates a cell whose content is rendered exactly as provided.
+    ///
+    /// Useful for pre-formatted content such as ASCII art or code snippets
+    pub fn verbatim<T>(data: T) -> TableCell
+    where
+        T: ToString,
+    {
+        Self {
+            data: data.to_string(),
+            col_span: 1,
+            row_span: 1,
+            alignment: Alignment::Left,
+            inherit_alignment: true,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
+            pad_content: false,
+            left_padding: 0,
+            right_padding: 0,
+            verbatim: true,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
+        }
+    }
+
+    /// Creates a right aligned cell, which lines up the digits of numbers in a column.
+    ///
+    /// Numbers converted into cells, such as the `1` in `row![1, "x"]`, use this
+    pub fn number<T>(data: T) -> TableCell
+    where
+        T: ToString,
+    {
+        Self {
+            alignment: Alignment::Right,
+            inherit_alignment: false,
+            ..Self::new(data)
         }
     }
 
@@ -55,8 +286,26 @@ impl TableCell {
         Self {
             data: data.to_string(),
             alignment: Alignment::Left,
+            inherit_alignment: true,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
             pad_content: true,
+            left_padding: 1,
+            right_padding: 1,
+            verbatim: false,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
             col_span,
+            row_span: 1,
         }
     }
 
@@ -68,8 +317,26 @@ impl TableCell {
         Self {
             data: data.to_string(),
             pad_content: true,
+            left_padding: 1,
+            right_padding: 1,
+            verbatim: false,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
             col_span,
+            row_span: 1,
             alignment,
+            inherit_alignment: false,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
         }
     }
 
@@ -86,105 +353,524 @@ impl TableCell {
         Self {
             data: data.to_string(),
             col_span,
+            row_span: 1,
             alignment,
+            inherit_alignment: false,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
             pad_content,
+            left_padding: 1,
+            right_padding: 1,
+            verbatim: false,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
+        }
+    }
+
+    /// The alignment set on the cell itself, or `None` when it inherits one
+    pub(crate) fn own_alignment(&self) -> Option<Alignment> {
+        if self.inherit_alignment {
+            None
+        } else {
+            Some(self.alignment)
         }
     }
 
+    /// Sets the alignment of the cell itself, or makes it inherit one when `None`
+    pub(crate) fn set_own_alignment(&mut self, alignment: Option<Alignment>) {
+        self.alignment = alignment.unwrap_or(Alignment::Left);
+        self.inherit_alignment = alignment.is_none();
+    }
+
     /// Calculates the width of the cell.
     ///
     /// New line characters are taken into account during the calculation.
     pub fn width(&self) -> usize {
-        let wrapped = self.wrapped_content(std::usize::MAX);
+        let wrapped = self.wrapped_content(usize::MAX);
         let mut max = 0;
         for s in wrapped {
-            let str_width = string_width(&s);
+            let str_width = self.text_width(&s);
             max = cmp::max(max, str_width);
         }
         max
     }
 
+    /// Calculates the width of the cell like `width`, without building the lines of its content.
+    ///
+    /// Measures the widest line of the content along with the padding of the cell
+    pub fn natural_width(&self) -> usize {
+        // Unpadded sides are drawn with a null character, which takes up no space
+        let padding = if self.verbatim {
+            0
+        } else {
+            let (left_padding, right_padding) = self.paddings();
+            left_padding + right_padding
+        };
+        // Character wrapping keeps `\r\n` together as one grapheme, so it only breaks lines at `\n`
+        let splits_crlf = self.verbatim
+            || self.wrap_mode == WrapMode::Truncate
+            || self.wrap_style == WrapStyle::Word;
+
+        let mut escapes = STRIP_ANSI_RE.find_iter(&self.data).peekable();
+        let mut max_width = 0;
+        let mut width = 0;
+        // The position used to expand tabs, which only starts over after `\n`
+        let mut position = 0;
+        for (byte_index, grapheme) in self.data.grapheme_indices(true) {
+            while let Some(escape) = escapes.peek() {
+                if escape.end() > byte_index {
+                    break;
+                }
+                escapes.next();
+            }
+            if matches!(escapes.peek(), Some(escape) if escape.start() <= byte_index) {
+                continue;
+            }
+
+            // Tabs in verbatim content are kept, and are measured like other control characters
+            if grapheme == "\t" && !self.verbatim {
+                let spaces = if self.tab_width == 0 {
+                    0
+                } else {
+                    self.tab_width - position % self.tab_width
+                };
+                position += spaces;
+                width += spaces;
+            } else if grapheme == "\n" || (grapheme == "\r\n" && splits_crlf) {
+                max_width = cmp::max(max_width, width);
+                width = 0;
+                if grapheme == "\n" {
+                    position = 0;
+                }
+            } else {
+                let grapheme_width = grapheme_width(grapheme, self.ambiguous_wide);
+                position += grapheme_width;
+                width += grapheme_width;
+            }
+        }
+        cmp::max(max_width, width) + padding
+    }
+
     /// The width of the cell's content divided by its `col_span` value.
     pub fn split_width(&self) -> f32 {
-        let res = self.width() as f32 / self.col_span as f32;
-        res
+        self.natural_width() as f32 / self.col_span as f32
     }
 
     /// The minium width required to display the cell properly
     pub fn min_width(&self) -> usize {
-        let mut max_char_width: usize = 0;
-        for c in self.data.chars() {
-            max_char_width = cmp::max(max_char_width, c.width().unwrap_or(1) as usize);
+        // Verbatim content can't be wrapped so it always needs its full width
+        if self.verbatim || self.wrap_mode == WrapMode::NoWrap {
+            return self.natural_width();
         }
 
-        if self.pad_content {
-            max_char_width + ' '.width().unwrap_or(1) as usize * 2
-        } else {
-            max_char_width
+        let mut max_char_width: usize = 0;
+        for grapheme in self.data.graphemes(true) {
+            max_char_width = cmp::max(
+                max_char_width,
+                grapheme_width(grapheme, self.ambiguous_wide),
+            );
         }
+
+        let (left_pad, right_pad) = self.pad_strings();
+        max_char_width + self.text_width(&left_pad) + self.text_width(&right_pad)
+    }
+
+    /// The number of lines the cell's content takes up when it's wrapped to the provided width.
+    ///
+    /// The width includes the cell's padding, like the width passed to `wrapped_content`
+    pub fn height(&self, width: usize) -> usize {
+        self.wrapped_content(width).len()
     }
 
     /// Wraps the cell's content to the provided width.
     ///
     /// New line characters are taken into account.
     pub fn wrapped_content(&self, width: usize) -> Vec<String> {
-        let pad_char = if self.pad_content { ' ' } else { '\0' };
+        // Verbatim content is never changed, so its tabs aren't expanded either
+        if self.verbatim {
+            return self.data.split('\n').map(|line| line.to_string()).collect();
+        }
+        let data = expand_tabs(&self.data, self.tab_width, self.ambiguous_wide);
+
+        let (left_pad, right_pad) = self.pad_strings();
+        carry_sgr_state(self.wrap(&data, width), &left_pad, &right_pad)
+    }
+
+    /// The width of a string, counting ambiguous characters as wide if the cell does
+    fn text_width(&self, string: &str) -> usize {
+        display_width(string, self.ambiguous_wide)
+    }
+
+    /// The strings added before and after each line of content.
+    ///
+    /// Unpadded sides use a null character so every line still has a boundary on both sides
+    fn pad_strings(&self) -> (String, String) {
+        let pad = |padding: usize| {
+            if padding == 0 {
+                "\0".to_string()
+            } else {
+                " ".repeat(padding)
+            }
+        };
+        let (left_padding, right_padding) = self.paddings();
+        (pad(left_padding), pad(right_padding))
+    }
+
+    /// The number of spaces added before and after the content, which is none when `pad_content` is false
+    fn paddings(&self) -> (usize, usize) {
+        if self.pad_content {
+            (self.left_padding, self.right_padding)
+        } else {
+            (0, 0)
+        }
+    }
+
+    /// Applies the cell's colors to a rendered line of its content.
+    ///
+    /// Colors are applied again after any reset within the line and reset at the end of it
+    pub(crate) fn colorize(&self, line: String) -> String {
+        let colors: String = self
+            .fg
+            .map(|fg| fg.sgr(30))
+            .into_iter()
+            .chain(self.bg.map(|bg| bg.sgr(40)))
+            .collect();
+        if colors.is_empty() {
+            return line;
+        }
+        format!(
+            "{}{}{}",
+            colors,
+            line.replace(SGR_RESET, &format!("{}{}", SGR_RESET, colors)),
+            SGR_RESET
+        )
+    }
+
+    /// Wraps the cell's content according to its wrap mode and style, adding padding to each line
+    fn wrap(&self, data: &str, width: usize) -> Vec<String> {
+        let (left_pad, right_pad) = self.pad_strings();
+        let right_pad_width = self.text_width(&right_pad);
+
+        if self.wrap_mode == WrapMode::Truncate {
+            let pad_width = self.text_width(&left_pad) + right_pad_width;
+            return data
+                .split('\n')
+                .map(|line| {
+                    format!(
+                        "{}{}{}",
+                        left_pad,
+                        truncate(
+                            line,
+                            width.saturating_sub(pad_width),
+                            self.ambiguous_wide,
+                            self.truncation,
+                            &self.ellipsis
+                        ),
+                        right_pad
+                    )
+                })
+                .collect();
+        }
+
+        // Content that isn't wrapped is only split on new line characters
+        let width = if self.wrap_mode == WrapMode::NoWrap {
+            usize::MAX
+        } else {
+            width
+        };
+
+        if self.wrap_style == WrapStyle::Word {
+            return self.word_wrapped_content(data, width, &left_pad, &right_pad);
+        }
+
         let hidden: HashSet<usize> = STRIP_ANSI_RE
-            .find_iter(&self.data)
+            .find_iter(data)
             .flat_map(|m| m.start()..m.end())
             .collect();
         let mut res: Vec<String> = Vec::new();
         let mut buf = String::new();
-        buf.push(pad_char);
-        let mut byte_index = 0;
-        for c in self.data.chars() {
-            if !hidden.contains(&byte_index)
-                && (string_width(&buf) >= width - pad_char.width().unwrap_or(1) || c == '\n')
+        buf.push_str(&left_pad);
+        // Every line holds at least one character, even when the padding leaves no room for content
+        let mut has_content = false;
+        for (byte_index, grapheme) in data.grapheme_indices(true) {
+            let hidden = hidden.contains(&byte_index);
+            if !hidden
+                && ((has_content && self.text_width(&buf) >= width.saturating_sub(right_pad_width))
+                    || grapheme == "\n")
             {
-                buf.push(pad_char);
+                buf.push_str(&right_pad);
                 res.push(buf);
                 buf = String::new();
-                buf.push(pad_char);
-                if c == '\n' {
-                    byte_index += 1;
+                buf.push_str(&left_pad);
+                has_content = false;
+                if grapheme == "\n" {
                     continue;
                 }
             }
-            byte_index += c.len_utf8();
-            buf.push(c);
+            has_content |= !hidden;
+            buf.push_str(grapheme);
         }
-        buf.push(pad_char);
+        buf.push_str(&right_pad);
         res.push(buf);
 
         res
     }
+
+    /// Wraps the cell's content at whitespace, only breaking words that don't fit on a line of their own
+    fn word_wrapped_content(
+        &self,
+        data: &str,
+        width: usize,
+        left_pad: &str,
+        right_pad: &str,
+    ) -> Vec<String> {
+        // Use the same amount of space for content as character wrapping does
+        let available = width
+            .saturating_sub(self.text_width(left_pad))
+            .saturating_sub(self.text_width(right_pad));
+        let pad = |line: String| format!("{}{}{}", left_pad, line, right_pad);
+
+        let mut res = Vec::new();
+        for paragraph in data.split('\n') {
+            let mut buf = String::new();
+            for (i, word) in paragraph.split(' ').enumerate() {
+                let candidate = if i == 0 {
+                    word.to_string()
+                } else {
+                    format!("{} {}", buf, word)
+                };
+                if self.text_width(&candidate) <= available {
+                    buf = candidate;
+                    continue;
+                }
+
+                let mut word = word;
+                if i > 0 {
+                    if self.hyphenate {
+                        // Move the start of the word up to one of its hyphens onto the line when there's room
+                        let remaining = available.saturating_sub(self.text_width(&buf) + 1);
+                        let end = word
+                            .match_indices('-')
+                            .map(|(index, _)| index + 1)
+                            .take_while(|end| self.text_width(&word[..*end]) <= remaining)
+                            .last();
+                        if let Some(end) = end {
+                            buf = format!("{} {}", buf, &word[..end]);
+                            word = &word[end..];
+                        }
+                    }
+                    res.push(pad(buf));
+                }
+                let mut pieces = if self.hyphenate {
+                    hyphenate(word, available, self.ambiguous_wide)
+                } else {
+                    split_at_width(word, available, self.ambiguous_wide)
+                };
+                buf = pieces.pop().unwrap_or_default();
+                res.extend(pieces.into_iter().map(pad));
+            }
+            res.push(pad(buf));
+        }
+
+        res
+    }
 }
 
-impl<T> From<T> for TableCell
-where
-    T: ToString,
-{
-    fn from(other: T) -> Self {
-        TableCell::new(other)
+/// Replaces tab characters with spaces, up to the next multiple of `tab_width` on the line.
+///
+/// Ansi escape sequences don't move the position on the line
+fn expand_tabs(string: &str, tab_width: usize, ambiguous_wide: bool) -> String {
+    if !string.contains('\t') {
+        return string.to_string();
+    }
+
+    let hidden: HashSet<usize> = STRIP_ANSI_RE
+        .find_iter(string)
+        .flat_map(|m| m.start()..m.end())
+        .collect();
+    let mut res = String::new();
+    let mut position = 0;
+    for (byte_index, grapheme) in string.grapheme_indices(true) {
+        if hidden.contains(&byte_index) {
+            res.push_str(grapheme);
+        } else if grapheme == "\t" {
+            let spaces = if tab_width == 0 {
+                0
+            } else {
+                tab_width - position % tab_width
+            };
+            res.push_str(&" ".repeat(spaces));
+            position += spaces;
+        } else if grapheme == "\n" {
+            res.push_str(grapheme);
+            position = 0;
+        } else {
+            res.push_str(grapheme);
+            position += grapheme_width(grapheme, ambiguous_wide);
+        }
     }
+    res
 }
 
+/// Splits a string into pieces that are each no wider than `width`, ignoring ansi escape sequences
+fn split_at_width(string: &str, width: usize, ambiguous_wide: bool) -> Vec<String> {
+    let hidden: HashSet<usize> = STRIP_ANSI_RE
+        .find_iter(string)
+        .flat_map(|m| m.start()..m.end())
+        .collect();
+    let mut res = Vec::new();
+    let mut buf = String::new();
+    let mut buf_width = 0;
+    for (byte_index, grapheme) in string.grapheme_indices(true) {
+        if !hidden.contains(&byte_index) {
+            let grapheme_width = grapheme_width(grapheme, ambiguous_wide);
+            if buf_width + grapheme_width > width && buf_width > 0 {
+                res.push(buf);
+                buf = String::new();
+                buf_width = 0;
+            }
+            buf_width += grapheme_width;
+        }
+        buf.push_str(grapheme);
+    }
+    res.push(buf);
+    res
+}
+
+/// Splits a word into pieces that are each no wider than `width`, breaking it after the hyphens it contains
+/// when possible. Otherwise a hyphen is added to the end of each piece the word is broken into
+fn hyphenate(word: &str, width: usize, ambiguous_wide: bool) -> Vec<String> {
+    // There has to be room for at least one character before the hyphen
+    if width < 2 {
+        return split_at_width(word, width, ambiguous_wide);
+    }
+
+    let mut res = Vec::new();
+    let mut buf = String::new();
+    for part in word.split_inclusive('-') {
+        let candidate = format!("{}{}", buf, part);
+        if display_width(&candidate, ambiguous_wide) <= width {
+            buf = candidate;
+            continue;
+        }
+        if !buf.is_empty() {
+            res.push(buf);
+        }
+        let mut pieces = split_at_width(part, width - 1, ambiguous_wide);
+        buf = pieces.pop().unwrap_or_default();
+        res.extend(pieces.into_iter().map(|piece| format!("{}-", piece)));
+    }
+    res.push(buf);
+    res
+}
+
+macro_rules! impl_from_text {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for TableCell {
+                fn from(other: $t) -> Self {
+                    TableCell::new(other)
+                }
+            }
+        )*
+    };
+}
+
+// There is no blanket `From<T: ToString>` impl since it would conflict with the impls for numbers and `Option`.
+// Other types can be converted with `TableCell::new`
+impl_from_text!(
+    &str,
+    &String,
+    Cow<'_, str>,
+    Box<str>,
+    char,
+    &char,
+    bool,
+    &bool
+);
+
+impl From<String> for TableCell {
+    fn from(other: String) -> Self {
+        TableCell::from_string(other)
+    }
+}
+
+// Numbers are right aligned so their digits line up within a column
+macro_rules! impl_from_number {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for TableCell {
+                fn from(other: $t) -> Self {
+                    TableCell::number(other)
+                }
+            }
+
+            impl From<&$t> for TableCell {
+                fn from(other: &$t) -> Self {
+                    TableCell::number(other)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64);
+
 pub struct TableCellBuilder {
     data: String,
     col_span: usize,
-    alignment: Alignment,
+    row_span: usize,
+    alignment: Option<Alignment>,
+    vertical_alignment: VerticalAlignment,
+    wrap_mode: WrapMode,
+    wrap_style: WrapStyle,
     pad_content: bool,
+    left_padding: usize,
+    right_padding: usize,
+    verbatim: bool,
+    fg: Option<Color>,
+    bg: Option<Color>,
+    tab_width: usize,
+    center_bias: CenterBias,
+    ambiguous_wide: bool,
+    truncation: Truncation,
+    ellipsis: String,
+    hyphenate: bool,
+    tag: Option<String>,
+    width_override: Option<usize>,
+}
+
+// `None` becomes an empty cell, which tables draw using their `none_placeholder`
+impl<T> From<Option<T>> for TableCell
+where
+    T: Into<TableCell>,
+{
+    fn from(other: Option<T>) -> Self {
+        match other {
+            Some(value) => value.into(),
+            None => TableCell::new(""),
+        }
+    }
 }
 
-impl Into<TableCell> for TableCellBuilder {
-    fn into(self) -> TableCell {
-        self.build()
+impl From<TableCellBuilder> for TableCell {
+    fn from(builder: TableCellBuilder) -> TableCell {
+        builder.build()
     }
 }
 
-impl Into<TableCell> for &mut TableCellBuilder {
-    fn into(self) -> TableCell {
-        self.build()
+impl From<&mut TableCellBuilder> for TableCell {
+    fn from(builder: &mut TableCellBuilder) -> TableCell {
+        builder.build()
     }
 }
 
@@ -193,45 +879,338 @@ impl TableCellBuilder {
         TableCellBuilder {
             data,
             col_span: 1,
-            alignment: Alignment::Left,
+            row_span: 1,
+            alignment: None,
+            vertical_alignment: VerticalAlignment::Top,
+            wrap_mode: WrapMode::Wrap,
+            wrap_style: WrapStyle::Character,
             pad_content: true,
+            left_padding: 1,
+            right_padding: 1,
+            verbatim: false,
+            fg: None,
+            bg: None,
+            tab_width: 4,
+            width_override: None,
+            tag: None,
+            hyphenate: false,
+            truncation: Truncation::End,
+            ellipsis: ELLIPSIS.to_string(),
+            ambiguous_wide: false,
+            center_bias: CenterBias::Left,
         }
     }
 
+    /// The number of columns the cell occupies. Values less than 1 are treated as 1
     pub fn col_span(&mut self, col_span: usize) -> &mut Self {
-        self.col_span = col_span;
+        self.col_span = cmp::max(col_span, 1);
+        self
+    }
+
+    pub fn row_span(&mut self, row_span: usize) -> &mut Self {
+        self.row_span = row_span;
         self
     }
 
     pub fn alignment(&mut self, alignment: Alignment) -> &mut Self {
-        self.alignment = alignment;
+        self.alignment = Some(alignment);
+        self
+    }
+
+    pub fn vertical_alignment(&mut self, vertical_alignment: VerticalAlignment) -> &mut Self {
+        self.vertical_alignment = vertical_alignment;
+        self
+    }
+
+    pub fn wrap_mode(&mut self, wrap_mode: WrapMode) -> &mut Self {
+        self.wrap_mode = wrap_mode;
+        self
+    }
+
+    pub fn wrap_style(&mut self, wrap_style: WrapStyle) -> &mut Self {
+        self.wrap_style = wrap_style;
+        self
+    }
+
+    /// Attaches a label to the cell that isn't rendered
+    pub fn tag<T>(&mut self, tag: T) -> &mut Self
+    where
+        T: ToString,
+    {
+        self.tag = Some(tag.to_string());
+        self
+    }
+
+    /// Sets the width of the column the cell is in, including padding
+    pub fn width_override(&mut self, width_override: usize) -> &mut Self {
+        self.width_override = Some(width_override);
+        self
+    }
+
+    /// Whether words that are too wide for a line are broken with a hyphen when wrapping at words
+    pub fn hyphenate(&mut self, hyphenate: bool) -> &mut Self {
+        self.hyphenate = hyphenate;
+        self
+    }
+
+    /// Where content is cut off when the wrap mode is `WrapMode::Truncate`
+    pub fn truncation(&mut self, truncation: Truncation) -> &mut Self {
+        self.truncation = truncation;
+        self
+    }
+
+    /// Marks where content was cut off when the wrap mode is `WrapMode::Truncate`
+    pub fn ellipsis<T: ToString>(&mut self, ellipsis: T) -> &mut Self {
+        self.ellipsis = ellipsis.to_string();
         self
     }
 
+    /// Whether to add the padding to either side of the cell's content, which is a single space by default
     pub fn pad_content(&mut self, pad_content: bool) -> &mut Self {
         self.pad_content = pad_content;
         self
     }
 
+    /// The number of spaces to add to either side of the cell's content
+    pub fn padding(&mut self, padding: usize) -> &mut Self {
+        self.left_padding = padding;
+        self.right_padding = padding;
+        self
+    }
+
+    /// The number of spaces to add before the cell's content
+    pub fn left_padding(&mut self, left_padding: usize) -> &mut Self {
+        self.left_padding = left_padding;
+        self
+    }
+
+    /// The number of spaces to add after the cell's content
+    pub fn right_padding(&mut self, right_padding: usize) -> &mut Self {
+        self.right_padding = right_padding;
+        self
+    }
+
+    /// Render the cell's content exactly as provided, without padding or wrapping
+    pub fn verbatim(&mut self, verbatim: bool) -> &mut Self {
+        self.verbatim = verbatim;
+        self
+    }
+
+    /// The color of the cell's content
+    pub fn fg(&mut self, fg: Color) -> &mut Self {
+        self.fg = Some(fg);
+        self
+    }
+
+    /// The color behind the cell's content
+    pub fn bg(&mut self, bg: Color) -> &mut Self {
+        self.bg = Some(bg);
+        self
+    }
+
+    /// The side that gets the extra space when center aligned content can't be centered exactly
+    pub fn center_bias(&mut self, center_bias: CenterBias) -> &mut Self {
+        self.center_bias = center_bias;
+        self
+    }
+
+    /// Count East Asian characters of ambiguous width as two columns wide instead of one
+    pub fn ambiguous_wide(&mut self, ambiguous_wide: bool) -> &mut Self {
+        self.ambiguous_wide = ambiguous_wide;
+        self
+    }
+
+    /// The number of columns between tab stops
+    pub fn tab_width(&mut self, tab_width: usize) -> &mut Self {
+        self.tab_width = tab_width;
+        self
+    }
+
     pub fn build(&self) -> TableCell {
         TableCell {
             data: self.data.clone(),
             col_span: self.col_span,
-            alignment: self.alignment,
+            row_span: self.row_span,
+            alignment: self.alignment.unwrap_or(Alignment::Left),
+            inherit_alignment: self.alignment.is_none(),
+            vertical_alignment: self.vertical_alignment,
+            wrap_mode: self.wrap_mode,
+            wrap_style: self.wrap_style,
             pad_content: self.pad_content,
+            left_padding: self.left_padding,
+            right_padding: self.right_padding,
+            verbatim: self.verbatim,
+            fg: self.fg,
+            bg: self.bg,
+            tab_width: self.tab_width,
+            center_bias: self.center_bias,
+            ambiguous_wide: self.ambiguous_wide,
+            truncation: self.truncation,
+            ellipsis: self.ellipsis.clone(),
+            hyphenate: self.hyphenate,
+            tag: self.tag.clone(),
+            width_override: self.width_override,
         }
     }
 }
 
-// Taken from https://github.com/mitsuhiko/console
+// CSI sequences taken from https://github.com/mitsuhiko/console
+// OSC sequences, such as OSC 8 hyperlinks, are also matched so their payload isn't counted
 lazy_static! {
-    static ref STRIP_ANSI_RE: Regex =
-        Regex::new(r"[\x1b\x9b][\[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-PRZcf-nqry=><]")
-            .unwrap();
+    static ref STRIP_ANSI_RE: Regex = Regex::new(concat!(
+        r"\x1b\][^\x07\x1b]*(?:\x07|\x1b\\)",
+        r"|[\x1b\x9b][\[()#;?]*(?:[0-9]{1,4}(?:;[0-9]{0,4})*)?[0-9A-PRZcf-nqry=><]"
+    ))
+    .unwrap();
+    static ref SGR_RE: Regex = Regex::new(r"\x1b\[([0-9;]*)m").unwrap();
+}
+
+/// Makes each wrapped line self contained with regards to SGR (color and style) escape sequences.
+///
+/// Sequences still active at the end of a line are reset, then emitted again at the start of the next line.
+/// Lines are expected to begin with `left_pad` and end with `right_pad`
+fn carry_sgr_state(lines: Vec<String>, left_pad: &str, right_pad: &str) -> Vec<String> {
+    let mut active: Vec<String> = Vec::new();
+    lines
+        .into_iter()
+        .map(|line| {
+            if !line.contains('\x1b') && active.is_empty() {
+                return line;
+            }
+
+            let content = match line
+                .strip_prefix(left_pad)
+                .and_then(|rest| rest.strip_suffix(right_pad))
+            {
+                Some(content) => content,
+                None => return line,
+            };
+            let mut res = String::new();
+            res.push_str(left_pad);
+            res.push_str(&active.concat());
+            res.push_str(content);
+
+            for sgr in SGR_RE.captures_iter(content) {
+                let params = &sgr[1];
+                if params.is_empty() || params == "0" {
+                    active.clear();
+                } else if params.starts_with("0;") {
+                    active.clear();
+                    active.push(sgr[0].to_string());
+                } else {
+                    active.push(sgr[0].to_string());
+                }
+            }
+
+            if !active.is_empty() {
+                res.push_str(SGR_RESET);
+            }
+            res.push_str(right_pad);
+            res
+        })
+        .collect()
+}
+
+const SGR_RESET: &str = "\x1b[0m";
+
+/// Cuts off a line so that its width, including the ellipsis marking where it was cut, doesn't exceed `width`.
+///
+/// Ansi escape sequences are kept so that formatting is still reset after the cut
+fn truncate(
+    line: &str,
+    width: usize,
+    ambiguous_wide: bool,
+    truncation: Truncation,
+    ellipsis: &str,
+) -> String {
+    let line_width = display_width(line, ambiguous_wide);
+    if line_width <= width {
+        return line.to_string();
+    }
+
+    let hidden: HashSet<usize> = STRIP_ANSI_RE
+        .find_iter(line)
+        .flat_map(|m| m.start()..m.end())
+        .collect();
+    let ellipsis_width = display_width(ellipsis, ambiguous_wide);
+    let available = width.saturating_sub(ellipsis_width);
+    // The widths of content kept before and after the ellipsis
+    let (head, tail) = match truncation {
+        Truncation::End => (available, 0),
+        Truncation::Start => (0, available),
+        Truncation::Middle => (available - available / 2, available / 2),
+    };
+
+    let mut res = String::new();
+    let mut position = 0;
+    let mut truncated = false;
+    for (byte_index, grapheme) in line.grapheme_indices(true) {
+        if hidden.contains(&byte_index) {
+            res.push_str(grapheme);
+            continue;
+        }
+        let grapheme_width = grapheme_width(grapheme, ambiguous_wide);
+        let start = position;
+        position += grapheme_width;
+        if position <= head || start >= line_width - tail {
+            res.push_str(grapheme);
+        } else if !truncated {
+            if width >= ellipsis_width {
+                res.push_str(ellipsis);
+            }
+            truncated = true;
+        }
+    }
+    res
+}
+
+const ELLIPSIS: &str = "…";
+
+// Removes all ansi escape sequences from a string
+pub(crate) fn strip_ansi(string: &str) -> String {
+    STRIP_ANSI_RE.replace_all(string, "").into_owned()
+}
+
+/// Replaces control characters, other than new lines, tabs and those in ansi escape sequences,
+/// with `replacement`. They're removed when `replacement` is `None`
+pub(crate) fn replace_control_chars(string: &str, replacement: Option<char>) -> String {
+    let hidden: HashSet<usize> = STRIP_ANSI_RE
+        .find_iter(string)
+        .flat_map(|m| m.start()..m.end())
+        .collect();
+    let mut res = String::with_capacity(string.len());
+    for (byte_index, c) in string.char_indices() {
+        if c.is_control() && c != '\n' && c != '\t' && !hidden.contains(&byte_index) {
+            res.extend(replacement);
+        } else {
+            res.push(c);
+        }
+    }
+    res
 }
 
-// The width of a string. Strips ansi characters
+// The width of a string. Strips ansi characters, and other control characters are counted as taking up no space
 pub fn string_width(string: &str) -> usize {
+    display_width(string, false)
+}
+
+// The width of a string, optionally counting East Asian characters of ambiguous width as two columns wide
+pub(crate) fn display_width(string: &str, ambiguous_wide: bool) -> usize {
     let stripped = STRIP_ANSI_RE.replace_all(string, "");
-    stripped.width()
+    stripped
+        .graphemes(true)
+        .map(|grapheme| grapheme_width(grapheme, ambiguous_wide))
+        .sum()
+}
+
+// The width of a single grapheme cluster, such as an emoji sequence or a letter followed by combining marks
+fn grapheme_width(grapheme: &str, ambiguous_wide: bool) -> usize {
+    // Control characters, such as the null characters used when content isn't padded, take up no space
+    if grapheme.starts_with(char::is_control) {
+        0
+    } else if ambiguous_wide {
+        grapheme.width_cjk()
+    } else {
+        grapheme.width()
+    }
 }
@@ -0,0 +1,161 @@
+//! Derive macros for [term-table](https://crates.io/crates/term-table).
+//!
+//! These are re-exported by term-table when its `derive` feature is enabled.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitStr};
+
+/// Implements `term_table::TableRow` for a struct.
+///
+/// Each field becomes a cell containing its `Display` output, and the field names make up the header.
+/// Fields with a primitive number type, such as `u32` or `&f64`, are right aligned like other cells created from numbers.
+///
+/// Fields accept the following attributes:
+///
+/// * `#[table(rename = "Name")]` uses `Name` in the header instead of the field name
+/// * `#[table(align = "right")]` aligns the field's cells. One of `left`, `right`, `center`, `justify` or `decimal`
+#[proc_macro_derive(TableRow, attributes(table))]
+pub fn derive_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match table_row(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+/// The settings of a field taken from its `table` attributes
+struct Column {
+    accessor: TokenStream2,
+    name: String,
+    align: Option<TokenStream2>,
+    numeric: bool,
+}
+
+fn table_row(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(Error::new_spanned(
+                input,
+                "TableRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let columns = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .map(|field| {
+                let ident = field.ident.as_ref().unwrap();
+                column(field, quote!(#ident), ident.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Fields::Unnamed(fields) => fields
+            .unnamed
+            .iter()
+            .enumerate()
+            .map(|(i, field)| {
+                let index = syn::Index::from(i);
+                column(field, quote!(#index), i.to_string())
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+        Fields::Unit => Vec::new(),
+    };
+
+    let cells = columns.iter().map(|column| {
+        let accessor = &column.accessor;
+        match &column.align {
+            Some(align) => quote! {
+                ::term_table::table_cell::TableCell::builder(&self.#accessor)
+                    .alignment(::term_table::table_cell::Alignment::#align)
+                    .build()
+            },
+            None if column.numeric => quote! {
+                ::term_table::table_cell::TableCell::number(&self.#accessor)
+            },
+            None => quote! {
+                ::term_table::table_cell::TableCell::new(&self.#accessor)
+            },
+        }
+    });
+    let names = columns.iter().map(|column| &column.name);
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::term_table::TableRow for #ident #ty_generics #where_clause {
+            fn to_row(&self) -> ::term_table::row::Row {
+                let cells: ::std::vec::Vec<::term_table::table_cell::TableCell> = vec![#(#cells),*];
+                ::term_table::row::Row::new(cells)
+            }
+
+            fn header_row() -> ::term_table::row::Row {
+                let cells: ::std::vec::Vec<::term_table::table_cell::TableCell> =
+                    vec![#(::term_table::table_cell::TableCell::new(#names)),*];
+                ::term_table::row::Row::new(cells)
+            }
+        }
+    })
+}
+
+fn column(field: &syn::Field, accessor: TokenStream2, name: String) -> Result<Column, Error> {
+    let mut column = Column {
+        accessor,
+        name,
+        align: None,
+        numeric: is_numeric(&field.ty),
+    };
+
+    for attr in field
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("table"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                column.name = meta.value()?.parse::<LitStr>()?.value();
+                Ok(())
+            } else if meta.path.is_ident("align") {
+                let align = meta.value()?.parse::<LitStr>()?;
+                column.align = Some(match align.value().as_str() {
+                    "left" => quote!(Left),
+                    "right" => quote!(Right),
+                    "center" => quote!(Center),
+                    "justify" => quote!(Justify),
+                    "decimal" => quote!(Decimal),
+                    _ => {
+                        return Err(Error::new_spanned(
+                            align,
+                            "expected one of \"left\", \"right\", \"center\", \"justify\" or \"decimal\"",
+                        ))
+                    }
+                });
+                Ok(())
+            } else {
+                Err(meta.error("unsupported table attribute"))
+            }
+        })?;
+    }
+
+    Ok(column)
+}
+
+/// Whether a type is a primitive number or a reference to one
+fn is_numeric(ty: &syn::Type) -> bool {
+    const NUMBERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+    match ty {
+        syn::Type::Path(path) if path.qself.is_none() => {
+            matches!(path.path.get_ident(), Some(ident) if NUMBERS.iter().any(|number| ident == number))
+        }
+        syn::Type::Reference(reference) => is_numeric(&reference.elem),
+        syn::Type::Group(group) => is_numeric(&group.elem),
+        syn::Type::Paren(paren) => is_numeric(&paren.elem),
+        _ => false,
+    }
+}